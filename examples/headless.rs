@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use bevy_command_prompt::{ConsolePlugin, StdinConsolePlugin, prelude::*};
+
+/// Counts frames, so there is something to look at with `get`.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct Frames(u64);
+
+/// A windowless server which runs commands typed into the terminal, or piped
+/// in with e.g. `echo "get Frames" | cargo run --example headless`.
+pub fn main() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins((ConsolePlugin, StdinConsolePlugin { exit_on_eof: true }));
+    app.init_resource::<Frames>();
+    app.add_console_var("sv_name", "local".to_string(), "server name");
+    app.add_systems(Update, |mut frames: ResMut<Frames>| frames.0 += 1);
+    app.run();
+}
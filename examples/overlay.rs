@@ -0,0 +1,65 @@
+use bevy::{
+    diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_command_prompt::prelude::*;
+
+pub fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins);
+    // `diag watch fps` pins the frame rate to the top of the console.
+    app.add_plugins((
+        FrameTimeDiagnosticsPlugin::default(),
+        EntityCountDiagnosticsPlugin::default(),
+    ));
+    // Press ` (backtick) to open and close the console.
+    app.add_plugins(ConsoleOverlayPlugin::default());
+    app.add_systems(Startup, |mut commands: Commands| {
+        commands.spawn(Camera2d);
+        commands.spawn((
+            Sprite::from_color(Color::WHITE, Vec2::splat(32.)),
+            Transform::default(),
+        ));
+    });
+    app.add_systems(Update, move_player.run_if(console_not_focused));
+    // `player_speed 400` makes the sprite faster.
+    app.add_console_var_in("player_speed", 200f32, "sprite speed in px/s", 0.0..=1000.0);
+    // `run reset_player` moves the sprite back to the origin.
+    app.register_console_system("reset_player", reset_player);
+    // Runs `assets/autoexec.cfg` once it has loaded.
+    app.add_console_autoexec("autoexec.cfg");
+    app.run();
+}
+
+/// The overlay consumes keyboard input while it is open, so gating on
+/// [console_not_focused] is only needed when input capture is disabled.
+fn move_player(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    vars: Res<ConsoleVars>,
+    mut q_player: Query<&mut Transform, With<Sprite>>,
+) {
+    let mut dir = Vec2::ZERO;
+    if keys.pressed(KeyCode::KeyW) {
+        dir.y += 1.;
+    }
+    if keys.pressed(KeyCode::KeyS) {
+        dir.y -= 1.;
+    }
+    if keys.pressed(KeyCode::KeyA) {
+        dir.x -= 1.;
+    }
+    if keys.pressed(KeyCode::KeyD) {
+        dir.x += 1.;
+    }
+    let speed = vars.get::<f32>("player_speed").copied().unwrap_or_default();
+    for mut transform in q_player.iter_mut() {
+        transform.translation += (dir * speed * time.delta_secs()).extend(0.);
+    }
+}
+
+fn reset_player(mut q_player: Query<&mut Transform, With<Sprite>>) {
+    for mut transform in q_player.iter_mut() {
+        transform.translation = Vec3::ZERO;
+    }
+}
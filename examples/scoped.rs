@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_command_prompt::{ConsolePlugin, prelude::*};
+use clap::Parser;
+
+/// Says something in chat.
+#[derive(Parser, Message, Clone)]
+#[command(name = "say")]
+struct SayCmd {
+    message: Vec<String>,
+}
+
+fn on_say(mut reader: MessageReader<CommandMsg<SayCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        let message = format!("<player> {}", msg.command.message.join(" "));
+        msg.println(&mut commands, message);
+    }
+}
+
+pub fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins);
+    app.add_plugins(ConsolePlugin);
+    app.add_console_command::<SayCmd>();
+    app.add_systems(Update, on_say);
+    app.add_systems(Startup, |mut commands: Commands| {
+        commands.spawn(Camera2d);
+        commands.spawn((
+            Node {
+                width: Val::Vw(100.),
+                height: Val::Vh(100.),
+                ..Default::default()
+            },
+            children![
+                // The chat console can only talk and clear itself.
+                (
+                    Console::default().with_prompt("chat> ".into()),
+                    ConsoleCommandScope::new(["say", "clear"]),
+                ),
+                // The developer console can run everything.
+                Console::default().with_prompt("dev> ".into()),
+            ],
+        ));
+    });
+    app.run();
+}
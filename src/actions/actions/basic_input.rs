@@ -4,13 +4,12 @@ use crate::prelude::*;
 
 pub fn delete_char(input: In<ConsoleActionInput>, mut console_q: Query<&mut Console>) {
     if let Ok(mut console) = console_q.get_mut(input.console_id) {
-        console.input.pop();
+        console.delete_back();
     }
 }
 pub fn delete_word(input: In<ConsoleActionInput>, mut console_q: Query<&mut Console>) {
     if let Ok(mut console) = console_q.get_mut(input.console_id) {
-        let last_ws = console.input.rfind(char::is_whitespace).unwrap_or_default();
-        console.input.truncate(last_ws);
+        console.delete_word_back();
     }
 }
 
@@ -24,8 +24,12 @@ pub fn set_from_history(
     }
     if matches!(key, Key::ArrowUp | Key::ArrowDown) {
         let mut console = q_console.get_mut(input.console_id).unwrap();
+        // move between input lines before falling back to history
+        if console.move_cursor_lines(-value) {
+            return;
+        }
         if filtered_history.is_none() {
-            *original_value = Some(std::mem::take(&mut console.input));
+            *original_value = Some(console.input().to_string());
             let f = console
                 .history
                 .iter()
@@ -40,10 +44,11 @@ pub fn set_from_history(
         let ov = original_value.as_ref().unwrap();
         *history_idx = history_idx.saturating_add_signed(value).min(fh.len());
         if *history_idx == 0 {
-            console.input = ov.clone();
+            console.set_input(ov.clone());
         } else {
             let idx = fh[fh.len().saturating_sub(*history_idx + 1)];
-            console.input = console.history[idx].clone();
+            let value = console.history[idx].clone();
+            console.set_input(value);
         }
     }
 }
@@ -1,5 +1,10 @@
+use std::str::FromStr;
+
 use crate::prelude::*;
-use bevy::input::keyboard::Key;
+use bevy::{
+    input::keyboard::Key,
+    reflect::{DynamicEnum, DynamicVariant, TypeInfo, Typed},
+};
 use variadics_please::all_tuples;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Default, Asset, Reflect)]
@@ -57,6 +62,51 @@ impl ConsoleAction {
         }
     }
 
+    /// Returns the keys and modifiers which matched if the action was just
+    /// triggered, i.e. one key of every group was just pressed, one modifier
+    /// of every group is held and none of the excluded keys are.
+    pub fn matches(
+        &self,
+        keys: &ButtonInput<Key>,
+        key_codes: &ButtonInput<KeyCode>,
+    ) -> Option<(Vec<Key>, Vec<KeyCode>)> {
+        if self
+            .bad_keys
+            .iter()
+            .any(|or_group| or_group.iter().any(|key| keys.just_pressed(key.clone())))
+        {
+            return None;
+        }
+        if self
+            .bad_mods
+            .iter()
+            .any(|or_group| or_group.iter().any(|key_code| key_codes.pressed(*key_code)))
+        {
+            return None;
+        }
+
+        let matched_keys = self.keys.iter().try_fold(vec![], |mut res, or_group| {
+            or_group
+                .iter()
+                .find(|key| keys.just_pressed((**key).clone()))
+                .map(|found| {
+                    res.push(found.clone());
+                    res
+                })
+        });
+
+        let matched_mods = self.modifiers.iter().try_fold(vec![], |mut res, or_group| {
+            or_group
+                .iter()
+                .find(|key| key_codes.pressed(**key))
+                .map(|found| {
+                    res.push(*found);
+                    res
+                })
+        });
+        matched_keys.zip(matched_mods)
+    }
+
     /// Returns the key vec's only member, or None if there are multiple.
     pub fn get_single(&self) -> Option<Key> {
         if self.keys.len() == 1
@@ -70,6 +120,103 @@ impl ConsoleAction {
     }
 }
 
+/// Modifier names accepted by [ConsoleAction::from_str], in canonical order.
+const MODIFIERS: [(&[&str], [KeyCode; 2]); 4] = [
+    (
+        &["ctrl", "control"],
+        [KeyCode::ControlLeft, KeyCode::ControlRight],
+    ),
+    (&["alt", "option"], [KeyCode::AltLeft, KeyCode::AltRight]),
+    (&["shift"], [KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+    (
+        &["super", "meta", "cmd", "win"],
+        [KeyCode::SuperLeft, KeyCode::SuperRight],
+    ),
+];
+
+/// Parses a key name: a single character, an alias such as `esc` or `up`, or
+/// the name of a unit variant of [Key] such as `F5` or `PageDown`.
+/// Letters match regardless of case, so `r` still matches with shift held.
+fn parse_key(name: &str) -> Result<Vec<Key>, String> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        let mut keys = vec![Key::Character(c.to_lowercase().to_string().into())];
+        if c.is_alphabetic() {
+            keys.push(Key::Character(c.to_uppercase().to_string().into()));
+        }
+        return Ok(keys);
+    }
+    let name = match name.to_lowercase().as_str() {
+        "esc" => "Escape",
+        "return" => "Enter",
+        "del" => "Delete",
+        "ins" => "Insert",
+        "up" => "ArrowUp",
+        "down" => "ArrowDown",
+        "left" => "ArrowLeft",
+        "right" => "ArrowRight",
+        "pgup" => "PageUp",
+        "pgdn" => "PageDown",
+        "plus" => return Ok(vec![Key::Character("+".into())]),
+        _ => name,
+    };
+    let TypeInfo::Enum(info) = Key::type_info() else {
+        unreachable!("Key is an enum");
+    };
+    info.iter()
+        .find(|variant| {
+            variant.name().eq_ignore_ascii_case(name) && variant.as_unit_variant().is_ok()
+        })
+        .and_then(|variant| {
+            Key::from_reflect(&DynamicEnum::new(variant.name(), DynamicVariant::Unit))
+        })
+        .map(|key| vec![key])
+        .ok_or_else(|| format!("Unknown key '{name}'"))
+}
+
+/// Parses a key combination such as `F5`, `ctrl+shift+r` or `alt+Enter`.
+///
+/// The modifiers are `ctrl`, `alt`, `shift` and `super`, matching either
+/// side of the keyboard. Modifiers which aren't named must not be held, so
+/// `r` and `ctrl+r` never fire together.
+impl FromStr for ConsoleAction {
+    type Err = String;
+    fn from_str(combo: &str) -> Result<Self, String> {
+        let (mods, key) = match combo.rsplit_once('+') {
+            // `ctrl++`
+            Some((mods, "")) => (mods.strip_suffix('+').unwrap_or(mods), "+"),
+            Some((mods, key)) => (mods, key),
+            None => ("", combo),
+        };
+        let mods = mods
+            .split('+')
+            .filter(|m| !m.is_empty())
+            .map(|m| {
+                MODIFIERS
+                    .iter()
+                    .position(|(names, _)| names.iter().any(|n| n.eq_ignore_ascii_case(m)))
+                    .ok_or_else(|| format!("Unknown modifier '{m}'"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (with, without): (Vec<_>, Vec<_>) = MODIFIERS
+            .iter()
+            .enumerate()
+            .partition(|(i, _)| mods.contains(i));
+        Ok(ConsoleAction::new(parse_key(key)?)
+            .with_modifiers(
+                with.into_iter()
+                    .map(|(_, (_, codes))| codes.to_vec())
+                    .collect::<Vec<_>>(),
+            )
+            .without_modifiers(vec![
+                without
+                    .into_iter()
+                    .flat_map(|(_, (_, codes))| *codes)
+                    .collect::<Vec<_>>(),
+            ]))
+    }
+}
+
 // Wrapper types for Into implementations
 #[derive(Debug, Deref, DerefMut, Clone, PartialEq, Eq, Hash, Default, Reflect)]
 pub struct KeyInput(Vec<Vec<Key>>);
@@ -104,6 +251,13 @@ impl IntoKeyGroup for Vec<Key> {
 
 // Now implement KeyInput conversions
 
+// Single OR group
+impl From<Vec<Key>> for KeyInput {
+    fn from(keys: Vec<Key>) -> Self {
+        KeyInput(vec![keys])
+    }
+}
+
 // Single key
 impl From<Key> for KeyInput {
     fn from(key: Key) -> Self {
@@ -1,3 +1,11 @@
+use std::{fmt::Debug, ops::RangeInclusive, str::FromStr};
+
+use bevy::{
+    reflect::{GetTypeRegistration, Typed},
+    state::state::FreelyMutableState,
+};
+
+use super::scripts::ConsoleScripts;
 use crate::prelude::*;
 
 pub trait ConsoleCommand: clap::Parser + Send + Sync + 'static {}
@@ -6,19 +14,146 @@ impl<T> ConsoleCommand for T where T: clap::Parser + Send + Sync + 'static {}
 pub trait CommandExt {
     /// Registers a console command to the application.
     fn add_console_command<T: ConsoleCommand>(&mut self) -> &mut Self;
+    /// Like [Self::add_console_command], but only consoles whose
+    /// [ConsolePermissions] allow `level` can run the command.
+    ///
+    /// With the `strip_dev_commands` feature, [ConsoleLevel::Developer]
+    /// commands are left out of the console, so they can't be run at all.
+    fn add_console_command_at<T: ConsoleCommand>(&mut self, level: ConsoleLevel) -> &mut Self;
+    /// Registers a one-shot system which can be run from the console with
+    /// `run <name>`, without defining a command for it.
+    fn register_console_system<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self;
+    /// Makes a state available to the `state` command, e.g.
+    /// `state set GameState Paused`. Variants are parsed with [FromStr] and
+    /// listed through reflection.
+    fn add_console_state<S: FreelyMutableState + FromStr + Typed>(&mut self) -> &mut Self;
+    /// Registers a console variable, e.g.
+    /// `app.add_console_var("r_fov", 90., "field of view")`.
+    /// It can be read with `r_fov` and set with `r_fov 100` from the console,
+    /// and from systems through [ConsoleVars].
+    fn add_console_var<T: ConsoleVarValue>(
+        &mut self,
+        name: impl Into<String>,
+        default: T,
+        description: impl Into<String>,
+    ) -> &mut Self;
+    /// Like [Self::add_console_var], but rejects values outside of `range`.
+    fn add_console_var_in<T: ConsoleVarValue + PartialOrd + Debug>(
+        &mut self,
+        name: impl Into<String>,
+        default: T,
+        description: impl Into<String>,
+        range: RangeInclusive<T>,
+    ) -> &mut Self;
+    /// Exposes every field of a reflected resource as a console variable
+    /// named `<resource>.<field>`, e.g. `physics.gravity (0, -9.8, 0)` for
+    /// `Physics { gravity: Vec3 }`. Values are parsed as RON using the type of
+    /// the field and written straight into the resource.
+    fn expose_resource_to_console<
+        R: Resource + FromReflect + Typed + GetTypeRegistration + Default,
+    >(
+        &mut self,
+    ) -> &mut Self;
+    /// Runs a [ConsoleScript] in the first console once it has loaded, and
+    /// again whenever the file changes if bevy's `file_watcher` feature is
+    /// enabled. The path is relative to the assets folder, e.g. `autoexec.cfg`.
+    fn add_console_autoexec(&mut self, path: impl Into<String>) -> &mut Self;
 }
 impl CommandExt for App {
     // TODO: If a command is not initialized, it will panic.
     // Make sure to add a nice error message if possible.
     fn add_console_command<T: ConsoleCommand>(&mut self) -> &mut Self {
+        self.add_console_command_at::<T>(ConsoleLevel::User)
+    }
+    fn add_console_command_at<T: ConsoleCommand>(&mut self, level: ConsoleLevel) -> &mut Self {
+        // The message is still added, so systems reading it don't panic.
         self.add_message::<CommandMsg<T>>();
+        if cfg!(feature = "strip_dev_commands") && level == ConsoleLevel::Developer {
+            return self;
+        }
         let cmd = T::command().no_binary_name(true);
         let name = cmd.get_name();
         let dispatch = self.world_mut().register_system(dispatch_cmd::<T>);
         let mut cmds = self.world_mut().resource_mut::<ConsoleCommands>();
-        cmds.insert(name.to_string(), ConcreteConsoleCommand { cmd, dispatch });
+        cmds.insert(
+            name.to_string(),
+            ConcreteConsoleCommand {
+                cmd,
+                dispatch,
+                level,
+            },
+        );
+        self
+    }
+    fn register_console_system<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self {
+        let id = self.world_mut().register_system(system);
+        self.world_mut()
+            .get_resource_or_init::<ConsoleSystems>()
+            .insert(name.into(), id);
+        self
+    }
+    fn add_console_state<S: FreelyMutableState + FromStr + Typed>(&mut self) -> &mut Self {
+        super::commands::state::add_console_state::<S>(self);
         self
     }
+    fn add_console_var<T: ConsoleVarValue>(
+        &mut self,
+        name: impl Into<String>,
+        default: T,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        insert_console_var::<T>(self, name.into(), ConsoleVar::new(default, description))
+    }
+    fn add_console_var_in<T: ConsoleVarValue + PartialOrd + Debug>(
+        &mut self,
+        name: impl Into<String>,
+        default: T,
+        description: impl Into<String>,
+        range: RangeInclusive<T>,
+    ) -> &mut Self {
+        let var = ConsoleVar::new(default, description).with_range(range);
+        insert_console_var::<T>(self, name.into(), var)
+    }
+    fn expose_resource_to_console<
+        R: Resource + FromReflect + Typed + GetTypeRegistration + Default,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        self.register_type::<R>();
+        self.register_type_data::<R, ReflectResource>();
+        let vars = ConsoleVar::resource_fields::<R>();
+        self.world_mut()
+            .get_resource_or_init::<ConsoleVars>()
+            .extend(vars);
+        self
+    }
+    fn add_console_autoexec(&mut self, path: impl Into<String>) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<ConsoleScripts>()
+            .exec(path, None, true);
+        self
+    }
+}
+
+fn insert_console_var<T: ConsoleVarValue>(
+    app: &mut App,
+    name: String,
+    var: ConsoleVar,
+) -> &mut App {
+    app.register_type::<T>();
+    app.add_message::<CvarChanged<T>>();
+    app.world_mut()
+        .get_resource_or_init::<ConsoleVars>()
+        .insert(name, var);
+    app
 }
 
 fn dispatch_cmd<T: ConsoleCommand>(
@@ -36,6 +171,7 @@ fn dispatch_cmd<T: ConsoleCommand>(
         Ok(())
     })();
     if let Err(e) = res {
+        set_console_status(&mut commands, input.console_id, 2);
         commands.trigger(ConsolePrintln {
             message: e,
             console_id: input.console_id,
@@ -0,0 +1,166 @@
+use bevy::input::{InputSystems, keyboard::Key};
+use clap::Parser;
+
+use super::join_command_line;
+use crate::{commands::scripts::save_script, prelude::*};
+
+/// Binds a key combination to a command line which runs in the console it was
+/// bound in whenever the keys are pressed while no console has focus.
+///
+/// e.g. `bind F5 "save quick"` or `bind ctrl+shift+r reset_player`.
+/// Lists every binding as `bind` commands when no keys are given, which can
+/// be saved with `--save` and loaded again with `exec`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "bind")]
+pub struct BindCmd {
+    /// A key such as `F5`, `r` or `PageDown`, with optional `ctrl`, `alt`,
+    /// `shift` and `super` modifiers joined with `+`.
+    pub keys: Option<String>,
+    /// The command line to run. Multiple arguments are joined with spaces.
+    #[arg(num_args = 1.., allow_hyphen_values = true)]
+    pub command: Vec<String>,
+    /// Writes every binding as `bind` commands to a `.cfg` or `.console` file
+    /// in the assets folder, which can be run with `exec`. Needs the
+    /// Developer permission level.
+    #[arg(short, long, conflicts_with_all = ["keys", "command"])]
+    pub save: Option<String>,
+}
+
+/// Removes the binding for a key combination.
+#[derive(Parser, Message, Clone)]
+#[command(name = "unbind")]
+pub struct UnbindCmd {
+    #[arg(required_unless_present = "all")]
+    pub keys: Option<String>,
+    /// Removes every binding.
+    #[arg(short, long)]
+    pub all: bool,
+}
+
+fn parse_keys(keys: &str) -> Result<ConsoleAction, String> {
+    keys.parse::<ConsoleAction>()
+        .map_err(|e| format!("Invalid key combination '{keys}': {e}"))
+}
+
+fn bind_inner(input: In<CommandMsg<BindCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let BindCmd {
+            keys,
+            command,
+            save,
+        } = &input.command;
+        let Some(keys) = keys else {
+            let mut lines = world
+                .resource::<ConsoleBindings>()
+                .values()
+                .map(ConsoleBinding::to_command)
+                .collect::<Vec<_>>();
+            lines.sort();
+            if let Some(path) = save {
+                if world
+                    .get::<ConsolePermissions>(input.console_id)
+                    .is_some_and(|p| !p.allows(ConsoleLevel::Developer))
+                {
+                    return Err(format!(
+                        "Saving bindings needs the {} permission level",
+                        ConsoleLevel::Developer
+                    ));
+                }
+                save_script(world, path, &(lines.join("\n") + "\n"))?;
+                return Ok(format!("Saved {} bindings to {path}", lines.len()));
+            }
+            if lines.is_empty() {
+                return Err("No keys are bound".to_string());
+            }
+            return Ok(lines.join("\n"));
+        };
+        let action = parse_keys(keys)?;
+        let mut bindings = world.resource_mut::<ConsoleBindings>();
+        if command.is_empty() {
+            return bindings
+                .get(&action)
+                .map(ConsoleBinding::to_command)
+                .ok_or_else(|| format!("{keys} is not bound"));
+        }
+        let command = join_command_line(command)?;
+        let binding = ConsoleBinding {
+            keys: keys.clone(),
+            command,
+            console_id: input.console_id,
+        };
+        let out = binding.to_command();
+        bindings.insert(action, binding);
+        Ok(out)
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn unbind_inner(input: In<CommandMsg<UnbindCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let mut bindings = world.resource_mut::<ConsoleBindings>();
+        if input.command.all {
+            let count = bindings.len();
+            bindings.clear();
+            return Ok(format!("Removed {count} bindings"));
+        }
+        let keys = input.command.keys.as_deref().unwrap_or_default();
+        bindings
+            .remove(&parse_keys(keys)?)
+            .map(|binding| format!("Unbound {}", binding.keys))
+            .ok_or_else(|| format!("{keys} is not bound"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+/// Runs the command lines whose keys were just pressed. Bindings whose
+/// console has been despawned are dropped.
+fn fire_bindings(
+    keys: Res<ButtonInput<Key>>,
+    key_codes: Res<ButtonInput<KeyCode>>,
+    mut bindings: ResMut<ConsoleBindings>,
+    console_q: Query<(), With<Console>>,
+    mut commands: Commands,
+) {
+    bindings.retain(|action, binding| {
+        if !console_q.contains(binding.console_id) {
+            return false;
+        }
+        if action.matches(&keys, &key_codes).is_some() {
+            commands.trigger(CallCommandEvent {
+                command_name: binding.command.clone(),
+                console_id: binding.console_id,
+            });
+        }
+        true
+    });
+}
+
+fn on_bind_msg(mut reader: MessageReader<CommandMsg<BindCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(bind_inner, msg.clone());
+    }
+}
+
+fn on_unbind_msg(mut reader: MessageReader<CommandMsg<UnbindCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(unbind_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleBindings>();
+    app.add_systems(
+        PreUpdate,
+        (
+            on_bind_msg,
+            on_unbind_msg,
+            fire_bindings.after(InputSystems).run_if(
+                console_not_focused
+                    .and(resource_exists::<ButtonInput<Key>>)
+                    .and(resource_exists::<ButtonInput<KeyCode>>),
+            ),
+        ),
+    );
+    app.add_console_command::<BindCmd>();
+    app.add_console_command::<UnbindCmd>();
+}
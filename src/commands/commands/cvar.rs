@@ -0,0 +1,203 @@
+use std::any::TypeId;
+
+use bevy::reflect::{PartialReflect, TypeRegistration, TypeRegistry};
+use clap::{Parser, Subcommand};
+
+use crate::{
+    commands::{reflection::*, table::console_extent},
+    prelude::*,
+};
+
+/// Reads and changes console variables registered with `add_console_var` or
+/// `expose_resource_to_console`.
+///
+/// Variables can also be read with `<name>` and set with `<name> <value>`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "cvar")]
+pub struct CvarCmd {
+    #[command(subcommand)]
+    pub action: CvarAction,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum CvarAction {
+    /// Lists every variable whose name contains the filter.
+    List { filter: Option<String> },
+    /// Prints a variable along with its description.
+    Get { name: String },
+    /// Sets a variable from RON. Multiple arguments are joined with spaces.
+    Set {
+        name: String,
+        #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+        value: Vec<String>,
+    },
+    /// Restores the default value of a variable.
+    Reset { name: String },
+}
+
+/// Parses a RON value, accepting unquoted strings for `String` variables.
+fn parse_var(
+    input: &str,
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn PartialReflect>, String> {
+    if registration.type_id() == TypeId::of::<String>() && ron::from_str::<String>(input).is_err() {
+        return Ok(Box::new(input.to_string()));
+    }
+    parse_value(input, registration, registry)
+}
+
+fn cvar_inner(input: In<CommandMsg<CvarCmd>>, world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let extent = console_extent(world, input.console_id);
+    let permissions = world.get::<ConsolePermissions>(input.console_id).copied();
+    let mut refused = false;
+    let res = world.resource_scope(|world, mut vars: Mut<ConsoleVars>| {
+        let unknown = |name: &str| format!("Unknown variable '{name}'");
+        // Setting and resetting need the variable's level, like commands do.
+        let mut check_level = |name: &str, var: &ConsoleVar| match permissions {
+            Some(permissions) if !permissions.allows(var.level) => {
+                refused = true;
+                Err(permissions.refusal(name, var.level))
+            }
+            _ => Ok(()),
+        };
+        match &input.command.action {
+            CvarAction::List { filter } => {
+                let mut table =
+                    ConsoleTable::new(["name", "value", "default", "range", "description"]);
+                for (name, var) in vars.iter() {
+                    if filter.as_ref().is_some_and(|filter| !name.contains(filter)) {
+                        continue;
+                    }
+                    table.push([
+                        name.clone(),
+                        var.read(world)
+                            .map_or_else(|e| e, |value| format_value_inline(value, &registry)),
+                        format_value_inline(var.default_value(), &registry),
+                        var.range.clone().unwrap_or_default(),
+                        var.description.clone(),
+                    ]);
+                }
+                table.sort_by("name", false)?;
+                Ok(table.render(extent.map_or(usize::MAX, |(columns, _)| columns)))
+            }
+            CvarAction::Get { name } => {
+                let var = vars.var(name).ok_or_else(|| unknown(name))?;
+                let mut out = format!(
+                    "{name} = {}\n  {}\n  default: {}",
+                    format_value_inline(var.read(world)?, &registry),
+                    var.description,
+                    format_value_inline(var.default_value(), &registry)
+                );
+                if let Some(range) = var.range.as_ref() {
+                    out += &format!(", range: {range}");
+                }
+                Ok(out)
+            }
+            CvarAction::Set { name, value } => {
+                let var = vars.get_mut(name).ok_or_else(|| unknown(name))?;
+                check_level(name, var)?;
+                let registration = registry
+                    .get(var.type_id)
+                    .ok_or_else(|| format!("The type of '{name}' is not registered"))?;
+                let value = parse_var(&value.join(" "), registration, &registry)?;
+                var.write(world, name, value.as_ref())?;
+                Ok(format!(
+                    "{name} = {}",
+                    format_value_inline(var.read(world)?, &registry)
+                ))
+            }
+            CvarAction::Reset { name } => {
+                let var = vars.get_mut(name).ok_or_else(|| unknown(name))?;
+                check_level(name, var)?;
+                let default = var.default_value().to_dynamic();
+                var.write(world, name, default.as_ref())?;
+                Ok(format!(
+                    "{name} = {}",
+                    format_value_inline(var.read(world)?, &registry)
+                ))
+            }
+        }
+    });
+    input.reply(&mut world.commands(), res);
+    if refused {
+        set_console_status(&mut world.commands(), input.console_id, 126);
+    }
+}
+
+fn on_cvar_msg(mut reader: MessageReader<CommandMsg<CvarCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(cvar_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleVars>();
+    app.add_systems(PreUpdate, on_cvar_msg);
+    app.add_console_command::<CvarCmd>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConsolePlugin;
+
+    #[derive(Resource, Reflect, Default)]
+    struct Physics {
+        gravity: f32,
+    }
+
+    /// Runs a line in a User console and returns its output and status.
+    fn run(line: &str) -> (String, i32) {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ConsolePlugin));
+        app.add_console_var("sv_name", "local".to_string(), "server name");
+        app.expose_resource_to_console::<Physics>();
+        app.init_resource::<Physics>();
+        app.finish();
+        app.cleanup();
+        let console_id = app
+            .world_mut()
+            .spawn((
+                Console::default(),
+                ConsolePermissions::new(ConsoleLevel::User),
+            ))
+            .id();
+        app.world_mut().trigger(CallCommandEvent {
+            command_name: line.to_string(),
+            console_id,
+        });
+        for _ in 0..3 {
+            app.update();
+        }
+        let world = app.world();
+        (
+            world.get::<Console>(console_id).unwrap().buffer.clone(),
+            world.get::<ConsoleSessionVars>(console_id).unwrap().status,
+        )
+    }
+
+    #[test]
+    fn user_consoles_set_their_variables() {
+        let (buffer, status) = run("sv_name server");
+        assert!(buffer.contains("sv_name = \"server\""), "{buffer}");
+        assert_eq!(status, 0);
+    }
+
+    #[test]
+    fn resource_fields_need_cheat() {
+        for line in ["physics.gravity 1", "cvar reset physics.gravity"] {
+            let (buffer, status) = run(line);
+            assert!(
+                buffer.contains("'physics.gravity' needs the Cheat permission level"),
+                "{buffer}"
+            );
+            assert_eq!(status, 126);
+        }
+        let (buffer, status) = run("physics.gravity");
+        assert!(buffer.contains("physics.gravity = 0.0"), "{buffer}");
+        assert_eq!(status, 0);
+    }
+}
@@ -0,0 +1,242 @@
+use std::time::Duration;
+
+use bevy::diagnostic::{Diagnostic, DiagnosticsStore};
+use clap::{Parser, Subcommand};
+
+use crate::{commands::table::console_extent, prelude::*};
+
+/// Prints diagnostics from the [DiagnosticsStore], e.g. `diag` for every
+/// diagnostic or `diag fps` for one, and pins them to the top of the console
+/// with `diag watch fps`.
+///
+/// FPS and frame times need the `FrameTimeDiagnosticsPlugin` and the entity
+/// count needs the `EntityCountDiagnosticsPlugin`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "diag", args_conflicts_with_subcommands = true)]
+pub struct DiagCmd {
+    /// The path of a diagnostic, such as `fps` or `frame_time`.
+    pub path: Option<String>,
+    #[command(subcommand)]
+    pub action: Option<DiagAction>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum DiagAction {
+    /// Keeps a diagnostic pinned to the top of the console.
+    Watch { path: String },
+    /// Unpins a diagnostic, or every diagnostic if no path is given.
+    Unwatch { path: Option<String> },
+}
+
+/// Diagnostics pinned with `diag watch`, by console.
+#[derive(Resource, Debug, Default)]
+struct DiagWatches {
+    watches: Vec<(Entity, String)>,
+    /// Real time of the last update, which happens at most every
+    /// [WATCH_INTERVAL].
+    last_update: Duration,
+}
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+fn find_diagnostic<'s>(store: &'s DiagnosticsStore, path: &str) -> Result<&'s Diagnostic, String> {
+    store
+        .iter()
+        .find(|d| d.path().as_str() == path)
+        .or_else(|| {
+            store
+                .iter()
+                .find(|d| d.path().as_str().eq_ignore_ascii_case(path))
+        })
+        .ok_or_else(|| format!("Unknown diagnostic '{path}', see `diag` for a list"))
+}
+
+fn format_measure(diagnostic: &Diagnostic, value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |value| {
+        format!("{value:.2}{}", diagnostic.suffix)
+    })
+}
+
+/// Draws the last `len` values with block characters, scaled between the
+/// smallest and largest of them.
+fn sparkline(diagnostic: &Diagnostic, len: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let values = diagnostic.values().copied().collect::<Vec<_>>();
+    let values = &values[values.len().saturating_sub(len)..];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            let t = if max > min {
+                (value - min) / (max - min)
+            } else {
+                0.
+            };
+            BARS[(t * (BARS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+/// The pinned line for a watched diagnostic.
+fn watch_line(diagnostic: &Diagnostic) -> String {
+    format!(
+        "{}: {} (avg {})",
+        diagnostic.path(),
+        format_measure(diagnostic, diagnostic.value()),
+        format_measure(diagnostic, diagnostic.average()),
+    )
+}
+
+/// Pins or unpins a line and redraws the console, which stays scrolled to
+/// the bottom if it was.
+fn set_pin(world: &mut World, console_id: Entity, key: &str, line: Option<String>) {
+    let Some(view) = world.get::<ConsoleBufferView>(console_id).copied() else {
+        return;
+    };
+    let Some(mut console) = world.get_mut::<Console>(console_id) else {
+        return;
+    };
+    let at_bottom = view.is_at_bottom(&console);
+    match line {
+        Some(line) => console.pin(key, line),
+        None => {
+            console.unpin(key);
+        }
+    }
+    let view = if at_bottom {
+        view.jump_to_bottom(&console)
+    } else {
+        view
+    };
+    world.entity_mut(console_id).insert(view);
+}
+
+fn watch_key(path: &str) -> String {
+    format!("diag:{path}")
+}
+
+fn diag_inner(input: In<CommandMsg<DiagCmd>>, world: &mut World) {
+    let extent = console_extent(world, input.console_id);
+    let res = (|| -> Result<String, String> {
+        let store = world
+            .get_resource::<DiagnosticsStore>()
+            .ok_or("No diagnostics, add the DiagnosticsPlugin")?;
+        match (&input.command.path, &input.command.action) {
+            (None, None) => {
+                let mut table = ConsoleTable::new(["path", "current", "average", "history"])
+                    .with_align(1, ColumnAlign::Right)
+                    .with_align(2, ColumnAlign::Right);
+                for diagnostic in store.iter().filter(|d| d.is_enabled) {
+                    table.push([
+                        diagnostic.path().to_string(),
+                        format_measure(diagnostic, diagnostic.value()),
+                        format_measure(diagnostic, diagnostic.average()),
+                        sparkline(diagnostic, 20),
+                    ]);
+                }
+                if table.rows.is_empty() {
+                    return Err("No diagnostics have been registered".to_string());
+                }
+                table.sort_by("path", false)?;
+                Ok(table.render(extent.map_or(usize::MAX, |(columns, _)| columns)))
+            }
+            (Some(path), _) => {
+                let diagnostic = find_diagnostic(store, path)?;
+                let values = diagnostic.values().copied().collect::<Vec<_>>();
+                let min = values.iter().copied().reduce(f64::min);
+                let max = values.iter().copied().reduce(f64::max);
+                Ok(format!(
+                    "{}: {}\n  average: {}, smoothed: {}\n  history ({}/{}): {}\n  min: {}, max: {}",
+                    diagnostic.path(),
+                    format_measure(diagnostic, diagnostic.value()),
+                    format_measure(diagnostic, diagnostic.average()),
+                    format_measure(diagnostic, diagnostic.smoothed()),
+                    diagnostic.history_len(),
+                    diagnostic.get_max_history_length(),
+                    sparkline(diagnostic, extent.map_or(60, |(columns, _)| columns / 2)),
+                    format_measure(diagnostic, min),
+                    format_measure(diagnostic, max),
+                ))
+            }
+            (None, Some(DiagAction::Watch { path })) => {
+                let diagnostic = find_diagnostic(store, path)?;
+                let (path, line) = (diagnostic.path().to_string(), watch_line(diagnostic));
+                let mut watches = world.resource_mut::<DiagWatches>();
+                if !watches.watches.contains(&(input.console_id, path.clone())) {
+                    watches.watches.push((input.console_id, path.clone()));
+                }
+                set_pin(world, input.console_id, &watch_key(&path), Some(line));
+                Ok(format!("Watching {path}"))
+            }
+            (None, Some(DiagAction::Unwatch { path })) => {
+                let mut watches = world.resource_mut::<DiagWatches>();
+                let mut removed = vec![];
+                watches.watches.retain(|(console_id, watched)| {
+                    let matches = *console_id == input.console_id
+                        && path
+                            .as_ref()
+                            .is_none_or(|p| p.eq_ignore_ascii_case(watched));
+                    if matches {
+                        removed.push(watched.clone());
+                    }
+                    !matches
+                });
+                if removed.is_empty() {
+                    return Err(match path {
+                        Some(path) => format!("{path} is not watched"),
+                        None => "No diagnostics are watched".to_string(),
+                    });
+                }
+                for watched in removed.iter() {
+                    set_pin(world, input.console_id, &watch_key(watched), None);
+                }
+                Ok(format!("Unwatched {}", removed.join(", ")))
+            }
+        }
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+/// Refreshes the pinned lines of watched diagnostics. Watches whose console
+/// has been despawned are dropped.
+fn update_watches(world: &mut World) {
+    let now = world.resource::<Time<Real>>().elapsed();
+    let mut watches = world.resource_mut::<DiagWatches>();
+    if watches.watches.is_empty() || now - watches.last_update < WATCH_INTERVAL {
+        return;
+    }
+    watches.last_update = now;
+    let watched = std::mem::take(&mut watches.watches);
+    let mut kept = vec![];
+    for (console_id, path) in watched {
+        if world.get::<Console>(console_id).is_none() {
+            continue;
+        }
+        let line = world
+            .resource::<DiagnosticsStore>()
+            .iter()
+            .find(|d| d.path().as_str() == path)
+            .map(watch_line);
+        set_pin(world, console_id, &watch_key(&path), line);
+        kept.push((console_id, path));
+    }
+    world.resource_mut::<DiagWatches>().watches = kept;
+}
+
+fn on_diag_msg(mut reader: MessageReader<CommandMsg<DiagCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(diag_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<DiagWatches>();
+    app.add_systems(PreUpdate, on_diag_msg);
+    app.add_systems(
+        PostUpdate,
+        update_watches
+            .run_if(resource_exists::<DiagnosticsStore>.and(resource_exists::<Time<Real>>)),
+    );
+    app.add_console_command::<DiagCmd>();
+}
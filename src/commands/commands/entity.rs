@@ -0,0 +1,252 @@
+use clap::Parser;
+
+use crate::{
+    commands::{lookup::*, reflection::*},
+    prelude::*,
+};
+
+/// Spawns an entity with the given components.
+///
+/// Components are written as `Type(ron)`, e.g. `Transform(translation: (0, 1, 0))`,
+/// or as `Type` to use the component's default value. The console removes
+/// double quotes, so RON strings need single quotes around them, e.g.
+/// `spawn 'Name("bob")'`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "spawn")]
+pub struct SpawnCmd {
+    /// The words of the components, which are joined back together and split
+    /// on the spaces outside of brackets.
+    #[arg(allow_hyphen_values = true)]
+    pub components: Vec<String>,
+}
+
+/// Despawns an entity.
+#[derive(Parser, Message, Clone)]
+#[command(name = "despawn")]
+pub struct DespawnCmd {
+    /// An entity index such as `12v0`, a [Name], or `picked`.
+    pub entity: String,
+    /// Despawn the entity's children as well.
+    /// Otherwise they are detached and left in place.
+    #[arg(short, long)]
+    pub recursive: bool,
+}
+
+/// Inserts a component into an entity, replacing any existing value.
+#[derive(Parser, Message, Clone)]
+#[command(name = "insert")]
+pub struct InsertCmd {
+    /// An entity index such as `12v0`, a [Name], or `picked`.
+    pub entity: String,
+    pub component: String,
+    /// The RON value. The component's default is used if omitted.
+    #[arg(num_args = 0.., allow_hyphen_values = true)]
+    pub value: Vec<String>,
+}
+
+/// Removes a component from an entity.
+#[derive(Parser, Message, Clone)]
+#[command(name = "remove")]
+pub struct RemoveCmd {
+    /// An entity index such as `12v0`, a [Name], or `picked`.
+    pub entity: String,
+    pub component: String,
+}
+
+/// Splits `Type(ron)` into the type name and the value.
+/// A bare `Type` has no value.
+fn split_component(spec: &str) -> (&str, Option<&str>) {
+    let mut depth = 0usize;
+    for (i, c) in spec.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            '(' if depth == 0 => return (&spec[..i], Some(&spec[i..])),
+            _ => {}
+        }
+    }
+    (spec, None)
+}
+
+/// Joins the words of the `spawn` command and splits them into components
+/// on the spaces outside of brackets and strings, so that
+/// `Transform(translation: (0, 1, 0)) Name("bob")` is two components.
+fn split_components(words: &[String]) -> Vec<String> {
+    let mut components = vec![];
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in words.join(" ").chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 && !in_string => {
+                if !current.is_empty() {
+                    components.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        components.push(current);
+    }
+    components
+}
+
+/// Parses `Type(ron)`. RON allows the struct name in front of the value, so
+/// the whole spec is tried first, then the parenthesized value, then its
+/// contents for newtypes such as `Name("bob")`.
+fn parse_component(
+    spec: &str,
+    registry: &bevy::reflect::TypeRegistry,
+) -> Result<(ReflectComponent, Box<dyn PartialReflect>), String> {
+    let (name, value) = split_component(spec);
+    let (registration, reflect) = component_registration(registry, name)?;
+    let value = match value {
+        None => build_value(registration, None, registry)?,
+        Some(value) => parse_value(spec, registration, registry)
+            .or_else(|e| parse_value(value, registration, registry).map_err(|_| e.clone()))
+            .or_else(|e| {
+                let inner = value
+                    .strip_prefix('(')
+                    .and_then(|value| value.strip_suffix(')'))
+                    .ok_or(e.clone())?;
+                parse_value(inner, registration, registry).map_err(|_| e)
+            })?,
+    };
+    Ok((reflect.clone(), value))
+}
+
+fn spawn_inner(input: In<CommandMsg<SpawnCmd>>, world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let res = split_components(&input.command.components)
+        .iter()
+        .map(|spec| parse_component(spec, &registry))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|components| {
+            let mut entity = world.spawn_empty();
+            for (reflect, value) in components {
+                reflect.insert(&mut entity, value.as_ref(), &registry);
+            }
+            format!("Spawned {}", entity.id())
+        });
+    input.reply(&mut world.commands(), res);
+}
+
+fn despawn_inner(input: In<CommandMsg<DespawnCmd>>, world: &mut World) {
+    let res = resolve_entity(world, &input.command.entity).map(|entity| {
+        let mut entity_mut = world.entity_mut(entity);
+        if !input.command.recursive {
+            entity_mut.remove::<Children>();
+        }
+        entity_mut.despawn();
+        format!("Despawned {entity}")
+    });
+    input.reply(&mut world.commands(), res);
+}
+
+fn insert_inner(input: In<CommandMsg<InsertCmd>>, world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let res = (|| -> Result<String, String> {
+        let entity = resolve_entity(world, &input.command.entity)?;
+        let (registration, reflect) = component_registration(&registry, &input.command.component)?;
+        let value = input.command.value.join(" ");
+        let value = build_value(
+            registration,
+            (!value.is_empty()).then_some(value.as_str()),
+            &registry,
+        )?;
+        reflect.insert(&mut world.entity_mut(entity), value.as_ref(), &registry);
+        Ok(format!(
+            "Inserted {} into {entity}",
+            registration.type_info().type_path()
+        ))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn remove_inner(input: In<CommandMsg<RemoveCmd>>, world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let res = (|| -> Result<String, String> {
+        let entity = resolve_entity(world, &input.command.entity)?;
+        let (registration, reflect) = component_registration(&registry, &input.command.component)?;
+        let type_path = registration.type_info().type_path();
+        let mut entity_mut = world.entity_mut(entity);
+        if !reflect.contains(&entity_mut) {
+            return Err(format!("Entity {entity} has no {type_path}"));
+        }
+        reflect.remove(&mut entity_mut);
+        Ok(format!("Removed {type_path} from {entity}"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_spawn_msg(mut reader: MessageReader<CommandMsg<SpawnCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(spawn_inner, msg.clone());
+    }
+}
+
+fn on_despawn_msg(mut reader: MessageReader<CommandMsg<DespawnCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(despawn_inner, msg.clone());
+    }
+}
+
+fn on_insert_msg(mut reader: MessageReader<CommandMsg<InsertCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(insert_inner, msg.clone());
+    }
+}
+
+fn on_remove_msg(mut reader: MessageReader<CommandMsg<RemoveCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(remove_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        PreUpdate,
+        (on_spawn_msg, on_despawn_msg, on_insert_msg, on_remove_msg),
+    );
+    app.add_console_command_at::<SpawnCmd>(ConsoleLevel::Cheat);
+    app.add_console_command_at::<DespawnCmd>(ConsoleLevel::Cheat);
+    app.add_console_command_at::<InsertCmd>(ConsoleLevel::Cheat);
+    app.add_console_command_at::<RemoveCmd>(ConsoleLevel::Cheat);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(words: &[&str]) -> Vec<String> {
+        split_components(&words.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn unquoted_components_are_joined() {
+        assert_eq!(
+            split(&["Transform(translation:", "(0,", "1,", "0))", "Visibility"]),
+            ["Transform(translation: (0, 1, 0))", "Visibility"]
+        );
+    }
+
+    #[test]
+    fn quoted_components_are_kept() {
+        assert_eq!(
+            split(&["Transform(translation: (0, 1, 0))", "Name(\"a ) b\")"]),
+            ["Transform(translation: (0, 1, 0))", "Name(\"a ) b\")"]
+        );
+    }
+}
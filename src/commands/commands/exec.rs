@@ -0,0 +1,48 @@
+use clap::Parser;
+
+use crate::{commands::scripts::ConsoleScripts, prelude::*};
+
+/// Runs a console script, e.g. `exec tuning.cfg`.
+///
+/// Paths are relative to the assets folder. Every line is run as a command,
+/// see [ConsoleScript] for the syntax.
+#[derive(Parser, Message, Clone)]
+#[command(name = "exec")]
+pub struct ExecCmd {
+    pub path: String,
+    /// Runs the script again whenever the file changes.
+    /// Needs the `file_watcher` feature of bevy.
+    #[arg(short, long)]
+    pub watch: bool,
+}
+
+fn exec_inner(input: In<CommandMsg<ExecCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        if !world.contains_resource::<Assets<ConsoleScript>>() {
+            return Err("Scripts need the AssetPlugin".to_string());
+        }
+        let ExecCmd { path, watch } = input.command.clone();
+        world.get_resource_or_init::<ConsoleScripts>().exec(
+            path.clone(),
+            Some(input.console_id),
+            watch,
+        );
+        Ok(if watch {
+            format!("Watching {path}")
+        } else {
+            format!("Running {path}")
+        })
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_exec_msg(mut reader: MessageReader<CommandMsg<ExecCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(exec_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(PreUpdate, on_exec_msg);
+    app.add_console_command::<ExecCmd>();
+}
@@ -0,0 +1,97 @@
+use clap::Parser;
+use strum::IntoEnumIterator;
+
+use crate::{commands::table::console_extent, prelude::*};
+
+/// Lists the commands this console can run, or prints the help of one, e.g.
+/// `help spawn`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "help")]
+pub struct HelpCmd {
+    pub command: Option<String>,
+}
+
+/// Whether the console's [ConsoleCommandScope] allows a command.
+fn in_scope(world: &World, console_id: Entity, name: &str) -> bool {
+    world
+        .get::<ConsoleCommandScope>(console_id)
+        .is_none_or(|scope| scope.allows(name))
+}
+
+/// Whether the console's [ConsoleCommandScope] and [ConsolePermissions] allow
+/// a command.
+fn can_run(
+    world: &World,
+    console_id: Entity,
+    name: &str,
+    command: &ConcreteConsoleCommand,
+) -> bool {
+    in_scope(world, console_id, name)
+        && world
+            .get::<ConsolePermissions>(console_id)
+            .is_none_or(|p| p.allows(command.level))
+}
+
+/// The name, usage and description of every command the console can run,
+/// including builtins such as `clear`.
+pub(crate) fn commands_table(world: &World, console_id: Entity) -> ConsoleTable {
+    let mut table = ConsoleTable::new(["name", "usage", "about"]);
+    for (name, command) in world.resource::<ConsoleCommands>().iter() {
+        if !can_run(world, console_id, name, command) {
+            continue;
+        }
+        let usage = command.cmd.clone().render_usage().to_string();
+        table.push([
+            name.clone(),
+            usage.trim_start_matches("Usage: ").to_string(),
+            command
+                .cmd
+                .get_about()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        ]);
+    }
+    for builtin in ConsoleBuiltin::iter() {
+        let name = builtin.to_string();
+        if in_scope(world, console_id, &name) {
+            table.push([name.clone(), name, builtin.about().to_string()]);
+        }
+    }
+    table
+}
+
+fn help_inner(input: In<CommandMsg<HelpCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let Some(name) = input.command.command.as_ref() else {
+            let mut table = commands_table(world, input.console_id);
+            table.sort_by("name", false)?;
+            return Ok(table.render(
+                console_extent(world, input.console_id).map_or(usize::MAX, |(columns, _)| columns),
+            ));
+        };
+        world
+            .resource::<ConsoleCommands>()
+            .get(name)
+            .filter(|command| can_run(world, input.console_id, name, command))
+            .map(|command| command.cmd.clone().render_long_help().to_string())
+            .or_else(|| {
+                ConsoleBuiltin::iter()
+                    .find(|builtin| builtin.to_string() == *name)
+                    .filter(|_| in_scope(world, input.console_id, name))
+                    .map(|builtin| format!("{}\n\nUsage: {name}", builtin.about()))
+            })
+            .ok_or_else(|| format!("Unknown command '{name}'"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_help_msg(mut reader: MessageReader<CommandMsg<HelpCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(help_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(PreUpdate, on_help_msg);
+    app.add_console_command::<HelpCmd>();
+}
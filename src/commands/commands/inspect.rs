@@ -0,0 +1,161 @@
+use std::fmt::Write;
+
+use bevy::{ecs::component::ComponentId, reflect::TypeRegistry};
+use clap::Parser;
+
+use crate::{
+    commands::{lookup::*, reflection::*},
+    prelude::*,
+};
+
+/// Prints every component on an entity along with its hierarchy.
+#[derive(Parser, Message, Clone)]
+#[command(name = "inspect")]
+pub struct InspectCmd {
+    /// An entity index such as `12v0`, a [Name], or `picked` for the last
+    /// clicked entity.
+    #[arg(default_value = "picked")]
+    pub entity: String,
+    /// The output format.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+}
+
+/// The short names of the components in `components` which require `id`.
+fn required_by(world: &World, components: &[ComponentId], id: ComponentId) -> Vec<String> {
+    components
+        .iter()
+        .filter_map(|other| world.components().get_info(*other))
+        .filter(|other| other.required_components().iter_ids().any(|r| r == id))
+        .map(|other| other.name().shortname().to_string())
+        .collect()
+}
+
+fn inspect_json(world: &World, entity: Entity, registry: &TypeRegistry) -> serde_json::Value {
+    let entity_ref = world.entity(entity);
+    let archetype = entity_ref.archetype();
+    let components = archetype
+        .components()
+        .iter()
+        .map(|id| {
+            let info = world.components().get_info(*id).unwrap();
+            let value = info
+                .type_id()
+                .and_then(|type_id| registry.get_type_data::<ReflectComponent>(type_id))
+                .and_then(|reflect| reflect.reflect(entity_ref))
+                .map(|value| value_to_json(value.as_partial_reflect(), registry));
+            serde_json::json!({
+                "name": info.name().to_string(),
+                "required_by": required_by(world, archetype.components(), *id),
+                "value": value,
+            })
+        })
+        .collect::<Vec<_>>();
+    let children = entity_ref
+        .get::<Children>()
+        .into_iter()
+        .flat_map(|children| children.iter())
+        .map(|child| child.to_string())
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "entity": entity.to_string(),
+        "name": entity_ref.get::<Name>().map(|name| name.as_str()),
+        "archetype": archetype.id().index(),
+        "table": archetype.table_id().as_usize(),
+        "parent": entity_ref.get::<ChildOf>().map(|child_of| child_of.parent().to_string()),
+        "children": children,
+        "components": components,
+    })
+}
+
+fn inspect_inner(input: In<CommandMsg<InspectCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let entity = resolve_entity(world, &input.command.entity)?;
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        if input.command.format != OutputFormat::Text {
+            return input
+                .command
+                .format
+                .serialize(&inspect_json(world, entity, &registry));
+        }
+        let entity_ref = world.entity(entity);
+        let archetype = entity_ref.archetype();
+        let components = archetype.components();
+
+        let mut out = describe_entity(world, entity);
+        write!(
+            out,
+            "\narchetype: {:?}, table: {:?}",
+            archetype.id(),
+            archetype.table_id()
+        )
+        .unwrap();
+        if let Some(child_of) = entity_ref.get::<ChildOf>() {
+            write!(
+                out,
+                "\nparent: {}",
+                describe_entity(world, child_of.parent())
+            )
+            .unwrap();
+        }
+        if let Some(children) = entity_ref.get::<Children>() {
+            let children = children
+                .iter()
+                .map(|child| describe_entity(world, child))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(out, "\nchildren: {children}").unwrap();
+        }
+
+        write!(out, "\ncomponents ({}):", components.len()).unwrap();
+        for id in components {
+            let info = world.components().get_info(*id).unwrap();
+            write!(out, "\n  {}", info.name().shortname()).unwrap();
+            let required_by = required_by(world, components, *id);
+            if !required_by.is_empty() {
+                write!(out, " (required by {})", required_by.join(", ")).unwrap();
+            }
+            let value = info
+                .type_id()
+                .and_then(|type_id| registry.get_type_data::<ReflectComponent>(type_id))
+                .and_then(|reflect| reflect.reflect(entity_ref))
+                .map(|value| format_value(value.as_partial_reflect(), &registry));
+            match value {
+                Some(value) => {
+                    for line in value.lines() {
+                        write!(out, "\n    {line}").unwrap();
+                    }
+                }
+                None => write!(out, "\n    (not reflected)").unwrap(),
+            }
+        }
+        Ok(out)
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_inspect_msg(mut reader: MessageReader<CommandMsg<InspectCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(inspect_inner, msg.clone());
+    }
+}
+
+/// Remembers the last clicked entity which isn't part of a console.
+fn on_pick(
+    trigger: On<Pointer<Click>>,
+    q_ignored: Query<(), Or<(With<Console>, With<Window>)>>,
+    mut picked: ResMut<ConsolePickedEntity>,
+) {
+    let target = trigger.original_event_target();
+    if !q_ignored.contains(target) {
+        picked.0 = Some(target);
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ConsolePickedEntity>();
+    app.add_observer(on_pick);
+    app.add_systems(PreUpdate, on_inspect_msg);
+    app.add_console_command_at::<InspectCmd>(ConsoleLevel::Developer);
+}
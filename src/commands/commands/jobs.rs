@@ -0,0 +1,213 @@
+use clap::Parser;
+
+use super::join_command_line;
+use crate::{commands::table::console_extent, prelude::*};
+
+/// Runs a command line once after a delay, e.g. `after 2s "spawn boss"`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "after")]
+pub struct AfterCmd {
+    /// Frames, or virtual time such as `500ms`, `2s` or `1.5m`.
+    pub delay: ConsoleDelay,
+    /// Multiple arguments are joined with spaces.
+    #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+    pub command: Vec<String>,
+}
+
+/// Runs a command line repeatedly, e.g. `every 500ms "get Score"`.
+/// Stop it with `kill <id>`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "every")]
+pub struct EveryCmd {
+    /// Frames, or virtual time such as `500ms`, `2s` or `1.5m`.
+    pub interval: ConsoleDelay,
+    /// Multiple arguments are joined with spaces.
+    #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+    pub command: Vec<String>,
+}
+
+/// Lists the jobs started with `after` and `every`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "jobs")]
+pub struct JobsCmd;
+
+/// Cancels jobs started with `after` and `every`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "kill")]
+pub struct KillCmd {
+    #[arg(required_unless_present = "all")]
+    pub ids: Vec<u32>,
+    /// Cancels every job.
+    #[arg(short, long)]
+    pub all: bool,
+}
+
+/// Suspends a script, e.g. `wait 10` for ten frames or `wait 2s`.
+///
+/// Handled by the script runner, so this only reports an error when typed.
+#[derive(Parser, Message, Clone)]
+#[command(name = "wait")]
+pub struct WaitCmd {
+    pub delay: ConsoleDelay,
+}
+
+fn schedule(
+    world: &mut World,
+    console_id: Entity,
+    command: &[String],
+    delay: ConsoleDelay,
+    repeat: bool,
+) -> Result<String, String> {
+    let command = join_command_line(command)?;
+    let id = world
+        .resource_mut::<ConsoleJobs>()
+        .add(console_id, command, delay, repeat);
+    Ok(format!("[{id}]"))
+}
+
+fn after_inner(input: In<CommandMsg<AfterCmd>>, world: &mut World) {
+    let AfterCmd { delay, command } = &input.command;
+    let res = schedule(world, input.console_id, command, *delay, false);
+    input.reply(&mut world.commands(), res);
+}
+
+fn every_inner(input: In<CommandMsg<EveryCmd>>, world: &mut World) {
+    let EveryCmd { interval, command } = &input.command;
+    let res = schedule(world, input.console_id, command, *interval, true);
+    input.reply(&mut world.commands(), res);
+}
+
+fn jobs_inner(input: In<CommandMsg<JobsCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let jobs = world.resource::<ConsoleJobs>();
+        if jobs.jobs.is_empty() {
+            return Err("No jobs are running".to_string());
+        }
+        let mut table = ConsoleTable::new(["id", "kind", "delay", "remaining", "command"])
+            .with_align(0, ColumnAlign::Right);
+        for job in jobs.jobs.iter() {
+            table.push([
+                job.id.to_string(),
+                if job.repeat { "every" } else { "after" }.to_string(),
+                job.delay.to_string(),
+                job.remaining.to_string(),
+                job.command.clone(),
+            ]);
+        }
+        Ok(table.render(
+            console_extent(world, input.console_id).map_or(usize::MAX, |(columns, _)| columns),
+        ))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn kill_inner(input: In<CommandMsg<KillCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let mut jobs = world.resource_mut::<ConsoleJobs>();
+        if input.command.all {
+            let count = jobs.jobs.len();
+            jobs.jobs.clear();
+            return Ok(format!("Killed {count} jobs"));
+        }
+        // Nothing is killed unless every id exists.
+        if let Some(id) = input
+            .command
+            .ids
+            .iter()
+            .find(|id| !jobs.jobs.iter().any(|job| job.id == **id))
+        {
+            return Err(format!("No job with id {id}"));
+        }
+        let mut out = vec![];
+        jobs.jobs.retain(|job| {
+            let killed = input.command.ids.contains(&job.id);
+            if killed {
+                out.push(format!("Killed [{}] {}", job.id, job.command));
+            }
+            !killed
+        });
+        Ok(out.join("\n"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn wait_inner(input: In<CommandMsg<WaitCmd>>, world: &mut World) {
+    input.reply(
+        &mut world.commands(),
+        Err("wait can only be used in scripts".to_string()),
+    );
+}
+
+/// Counts down every job and runs the ones which are due. Jobs whose console
+/// has been despawned are dropped.
+fn run_jobs(
+    mut jobs: ResMut<ConsoleJobs>,
+    time: Res<Time<Virtual>>,
+    console_q: Query<(), With<Console>>,
+    mut commands: Commands,
+) {
+    jobs.jobs.retain_mut(|job| {
+        if !console_q.contains(job.console_id) {
+            return false;
+        }
+        if !job.remaining.tick(&time) {
+            return true;
+        }
+        commands.trigger(CallCommandEvent {
+            command_name: job.command.clone(),
+            console_id: job.console_id,
+        });
+        job.remaining = job.delay;
+        job.repeat
+    });
+}
+
+fn on_after_msg(mut reader: MessageReader<CommandMsg<AfterCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(after_inner, msg.clone());
+    }
+}
+
+fn on_every_msg(mut reader: MessageReader<CommandMsg<EveryCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(every_inner, msg.clone());
+    }
+}
+
+fn on_jobs_msg(mut reader: MessageReader<CommandMsg<JobsCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(jobs_inner, msg.clone());
+    }
+}
+
+fn on_kill_msg(mut reader: MessageReader<CommandMsg<KillCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(kill_inner, msg.clone());
+    }
+}
+
+fn on_wait_msg(mut reader: MessageReader<CommandMsg<WaitCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(wait_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleJobs>();
+    app.add_systems(
+        PreUpdate,
+        (
+            on_after_msg,
+            on_every_msg,
+            on_jobs_msg,
+            on_kill_msg,
+            on_wait_msg,
+        ),
+    );
+    app.add_systems(Update, run_jobs);
+    app.add_console_command::<AfterCmd>();
+    app.add_console_command::<EveryCmd>();
+    app.add_console_command::<JobsCmd>();
+    app.add_console_command::<KillCmd>();
+    app.add_console_command::<WaitCmd>();
+}
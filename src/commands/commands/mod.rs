@@ -1,7 +1,49 @@
 use crate::prelude::*;
 
+mod bind;
+pub(super) mod cvar;
+mod diag;
+mod entity;
+mod exec;
+mod help;
+#[cfg(not(feature = "strip_dev_commands"))]
+mod inspect;
+mod jobs;
+#[cfg(not(feature = "strip_dev_commands"))]
+mod query;
+mod reflect;
+mod run;
+mod session;
+#[cfg(not(feature = "strip_dev_commands"))]
 mod show;
+pub(super) mod state;
+mod time;
+#[cfg(not(feature = "strip_dev_commands"))]
+mod tree;
+
+/// Joins the arguments of commands which take a command line, so that
+/// `bind F5 "save quick"` and `bind F5 save quick` are equivalent.
+fn join_command_line(args: &[String]) -> Result<String, String> {
+    match args {
+        [line] => Ok(line.clone()),
+        args => shlex::try_join(args.iter().map(String::as_str)).map_err(|e| e.to_string()),
+    }
+}
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins(show::plugin);
+    app.add_plugins((
+        help::plugin,
+        cvar::plugin,
+        reflect::plugin,
+        entity::plugin,
+        run::plugin,
+        session::plugin,
+        exec::plugin,
+        bind::plugin,
+        jobs::plugin,
+        time::plugin,
+        diag::plugin,
+    ));
+    #[cfg(not(feature = "strip_dev_commands"))]
+    app.add_plugins((show::plugin, query::plugin, inspect::plugin, tree::plugin));
 }
@@ -0,0 +1,137 @@
+use clap::Parser;
+
+use crate::{
+    commands::{lookup::*, reflection::*, table::console_extent},
+    prelude::*,
+};
+
+/// Lists entities matching component filters.
+///
+/// e.g. `query --with Transform --with Name --without Camera --limit 20`
+#[derive(Parser, Message, Clone)]
+#[command(name = "query")]
+pub struct QueryCmd {
+    /// Only list entities with this component. Can be repeated.
+    #[arg(short, long)]
+    pub with: Vec<String>,
+    /// Only list entities without this component. Can be repeated.
+    #[arg(short = 'x', long)]
+    pub without: Vec<String>,
+    /// Print a reflected component value, e.g. `Transform.translation`.
+    /// Can be repeated.
+    #[arg(short, long)]
+    pub show: Vec<String>,
+    /// The maximum number of entities to list.
+    #[arg(short, long, default_value_t = 100)]
+    pub limit: usize,
+    /// The output format. Structured formats omit the entity count and
+    /// print values as data rather than RON strings.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+}
+
+fn query_inner(input: In<CommandMsg<QueryCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let cmd = &input.command;
+        let with = cmd
+            .with
+            .iter()
+            .map(|name| find_component(world, name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let without = cmd
+            .without
+            .iter()
+            .map(|name| find_component(world, name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let show = cmd
+            .show
+            .iter()
+            .map(|spec| {
+                let (name, path) = split_path(spec);
+                let (_, reflect) = component_registration(&registry, name)?;
+                Ok((spec.as_str(), reflect.clone(), path))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut builder = QueryBuilder::<Entity>::new(world);
+        for id in with {
+            builder.with_id(id);
+        }
+        for id in without {
+            builder.without_id(id);
+        }
+        let mut state = builder.build();
+        let entities = state.iter(world).collect::<Vec<_>>();
+
+        let limited = entities.iter().take(cmd.limit);
+        if matches!(cmd.format, OutputFormat::Json | OutputFormat::Ron) {
+            let records = limited
+                .map(|entity| {
+                    let mut record = serde_json::Map::new();
+                    record.insert("entity".into(), entity.to_string().into());
+                    let name = world.get::<Name>(*entity).map(|name| name.as_str());
+                    record.insert("name".into(), name.into());
+                    for (spec, reflect, path) in show.iter() {
+                        let value = reflect
+                            .reflect(world.entity(*entity))
+                            .and_then(|value| field_at(value, path).ok())
+                            .map(|field| value_to_json(field, &registry));
+                        record.insert(spec.to_string(), value.into());
+                    }
+                    record
+                })
+                .collect::<Vec<_>>();
+            return cmd.format.serialize(&records);
+        }
+
+        let headers = ["entity", "name"]
+            .into_iter()
+            .chain(show.iter().map(|(spec, ..)| *spec));
+        let mut table = ConsoleTable::new(headers);
+        for entity in limited {
+            let mut row = vec![
+                entity.to_string(),
+                world
+                    .get::<Name>(*entity)
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            ];
+            for (_, reflect, path) in show.iter() {
+                let value = reflect
+                    .reflect(world.entity(*entity))
+                    .ok_or("-".to_string())
+                    .and_then(|value| field_at(value, path))
+                    .map(|field| format_value_inline(field, &registry))
+                    .unwrap_or_else(|e| e);
+                row.push(value);
+            }
+            table.push(row);
+        }
+        let width =
+            console_extent(world, input.console_id).map_or(usize::MAX, |(columns, _)| columns);
+        let body = table.render_as(cmd.format, width)?;
+        Ok(match cmd.format {
+            OutputFormat::Text => format!(
+                "{body}\n{} of {} entities",
+                entities.len().min(cmd.limit),
+                entities.len()
+            ),
+            _ => body,
+        })
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_query_msg(mut reader: MessageReader<CommandMsg<QueryCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(query_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(PreUpdate, on_query_msg);
+    app.add_console_command_at::<QueryCmd>(ConsoleLevel::Developer);
+}
@@ -0,0 +1,224 @@
+use std::any::TypeId;
+
+use bevy::reflect::TypeRegistry;
+use clap::Parser;
+
+use crate::{
+    commands::{lookup::*, reflection::*},
+    prelude::*,
+};
+
+/// Prints a reflected resource or component value as RON.
+///
+/// Usage: `get <Resource>[.field.path]` or `get <entity> <Component>[.field.path]`
+#[cfg(not(feature = "strip_dev_commands"))]
+#[derive(Parser, Message, Clone)]
+#[command(name = "get")]
+pub struct GetCmd {
+    /// A resource path, or an entity index such as `12v0`, a [Name], or `picked`.
+    pub target: String,
+    /// The component path when the target is an entity.
+    pub component: Option<String>,
+    /// The output format. `text` and `ron` both print RON.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+}
+
+/// Sets a reflected resource or component value from RON.
+///
+/// Usage: `set <Resource>[.field.path] <value>` or
+/// `set <entity> <Component>[.field.path] <value>`
+#[derive(Parser, Message, Clone)]
+#[command(name = "set")]
+pub struct SetCmd {
+    /// A resource path, or an entity index such as `12v0`, a [Name], or `picked`.
+    pub target: String,
+    /// The RON value, preceded by the component path when the target is an
+    /// entity. Multiple arguments are joined with spaces.
+    #[arg(required = true, num_args = 1.., allow_hyphen_values = true)]
+    pub args: Vec<String>,
+}
+
+/// A reflected value addressed from the console.
+pub(crate) enum ReflectTarget {
+    Resource {
+        type_id: TypeId,
+        path: String,
+    },
+    Component {
+        entity: Entity,
+        type_id: TypeId,
+        path: String,
+    },
+}
+impl ReflectTarget {
+    /// Resolves `<Resource>[.path]` or `<entity> <Component>[.path]`, where the
+    /// entity is anything accepted by `inspect`.
+    /// Returns the target and whether `component` was used.
+    pub(crate) fn parse(
+        world: &mut World,
+        registry: &TypeRegistry,
+        target: &str,
+        component: Option<&str>,
+    ) -> Result<(Self, bool), String> {
+        let (name, path) = split_path(target);
+        let resource = find_registration(registry, name);
+        if parse_entity(world, target).is_none()
+            && let Ok(registration) = resource
+        {
+            if registration.data::<ReflectResource>().is_none() {
+                return Err(format!(
+                    "'{}' is not a reflected resource (missing #[reflect(Resource)])",
+                    registration.type_info().type_path()
+                ));
+            }
+            return Ok((
+                Self::Resource {
+                    type_id: registration.type_id(),
+                    path: path.to_string(),
+                },
+                false,
+            ));
+        }
+        let entity = resolve_entity(world, target).map_err(|e| match resource {
+            Err(_) if component.is_none() => format!("Resource '{name}' is not registered"),
+            _ => e,
+        })?;
+        let spec = component.ok_or("Missing component after entity")?;
+        let (name, path) = split_path(spec);
+        let (registration, _) = component_registration(registry, name)?;
+        Ok((
+            Self::Component {
+                entity,
+                type_id: registration.type_id(),
+                path: path.to_string(),
+            },
+            true,
+        ))
+    }
+
+    pub(crate) fn type_path(&self, registry: &TypeRegistry) -> &'static str {
+        let (Self::Resource { type_id, .. } | Self::Component { type_id, .. }) = self;
+        registry
+            .get(*type_id)
+            .map(|registration| registration.type_info().type_path())
+            .unwrap_or_default()
+    }
+
+    /// Reads the value and pretty-prints it in the given format.
+    #[cfg(not(feature = "strip_dev_commands"))]
+    pub(crate) fn read(
+        &self,
+        world: &World,
+        registry: &TypeRegistry,
+        format: OutputFormat,
+    ) -> Result<String, String> {
+        let type_path = self.type_path(registry);
+        let value = match self {
+            Self::Resource { type_id, .. } => registry
+                .get_type_data::<ReflectResource>(*type_id)
+                .unwrap()
+                .reflect(world)
+                .map_err(|e| format!("Could not read {type_path}: {e}"))?,
+            Self::Component {
+                entity, type_id, ..
+            } => registry
+                .get_type_data::<ReflectComponent>(*type_id)
+                .unwrap()
+                .reflect(world.entity(*entity))
+                .ok_or_else(|| format!("Entity {entity} has no {type_path}"))?,
+        };
+        let (Self::Resource { path, .. } | Self::Component { path, .. }) = self;
+        let field = field_at(value, path)?;
+        match format {
+            OutputFormat::Text | OutputFormat::Ron => Ok(format_value(field, registry)),
+            format => format.serialize(&value_to_json(field, registry)),
+        }
+    }
+
+    /// Parses and applies a RON value, returning the new value pretty-printed.
+    pub(crate) fn write(
+        &self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        input: &str,
+    ) -> Result<String, String> {
+        let type_path = self.type_path(registry);
+        let mut value = match self {
+            Self::Resource { type_id, .. } => registry
+                .get_type_data::<ReflectResource>(*type_id)
+                .unwrap()
+                .reflect_mut(&mut *world)
+                .map_err(|e| format!("Could not write {type_path}: {e}"))?,
+            Self::Component {
+                entity, type_id, ..
+            } => registry
+                .get_type_data::<ReflectComponent>(*type_id)
+                .unwrap()
+                .reflect_mut(world.entity_mut(*entity))
+                .ok_or_else(|| format!("Entity {entity} has no {type_path}"))?,
+        };
+        let (Self::Resource { path, .. } | Self::Component { path, .. }) = self;
+        let field = field_at_mut(&mut *value, path)?;
+        set_value(field, input, registry)?;
+        Ok(format_value(field, registry))
+    }
+}
+
+#[cfg(not(feature = "strip_dev_commands"))]
+fn get_inner(input: In<CommandMsg<GetCmd>>, world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let res = ReflectTarget::parse(
+        world,
+        &registry,
+        &input.command.target,
+        input.command.component.as_deref(),
+    )
+    .and_then(|(target, _)| target.read(world, &registry, input.command.format));
+    input.reply(&mut world.commands(), res);
+}
+
+#[cfg(not(feature = "strip_dev_commands"))]
+fn on_get_msg(mut reader: MessageReader<CommandMsg<GetCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(get_inner, msg.clone());
+    }
+}
+
+fn set_inner(input: In<CommandMsg<SetCmd>>, world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let args = &input.command.args;
+    let res = ReflectTarget::parse(
+        world,
+        &registry,
+        &input.command.target,
+        args.first().map(String::as_str),
+    )
+    .and_then(|(target, used_component)| {
+        let value = args[used_component as usize..].join(" ");
+        if value.is_empty() {
+            return Err("Missing value".to_string());
+        }
+        target.write(world, &registry, &value)
+    });
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_set_msg(mut reader: MessageReader<CommandMsg<SetCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(set_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(PreUpdate, on_set_msg);
+    app.add_console_command_at::<SetCmd>(ConsoleLevel::Cheat);
+    // Reading the world is only needed while developing.
+    #[cfg(not(feature = "strip_dev_commands"))]
+    {
+        app.add_systems(PreUpdate, on_get_msg);
+        app.add_console_command_at::<GetCmd>(ConsoleLevel::Developer);
+    }
+}
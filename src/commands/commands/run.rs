@@ -0,0 +1,46 @@
+use clap::Parser;
+
+use crate::prelude::*;
+
+/// Runs a system registered with `register_console_system`.
+///
+/// Lists the registered systems when no name is given.
+#[derive(Parser, Message, Clone)]
+#[command(name = "run")]
+pub struct RunCmd {
+    pub name: Option<String>,
+}
+
+fn run_inner(input: In<CommandMsg<RunCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let systems = world.get_resource_or_init::<ConsoleSystems>();
+        let Some(name) = input.command.name.as_ref() else {
+            let mut names = systems.keys().cloned().collect::<Vec<_>>();
+            if names.is_empty() {
+                return Err("No systems have been registered".to_string());
+            }
+            names.sort();
+            return Ok(names.join("\n"));
+        };
+        let id = *systems
+            .get(name)
+            .ok_or_else(|| format!("Unknown system '{name}'"))?;
+        world
+            .run_system(id)
+            .map_err(|e| format!("Failed to run '{name}': {e}"))?;
+        Ok(format!("Ran {name}"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_run_msg(mut reader: MessageReader<CommandMsg<RunCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(run_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleSystems>();
+    app.add_systems(PreUpdate, on_run_msg);
+    app.add_console_command_at::<RunCmd>(ConsoleLevel::Cheat);
+}
@@ -0,0 +1,110 @@
+use clap::Parser;
+
+use crate::prelude::*;
+
+/// Sets a session variable of the console, e.g. `let target 12v0`, which is
+/// substituted into command lines as `$target` or `${target}`.
+///
+/// Prints a variable when no value is given, and lists every variable when
+/// no name is given.
+#[derive(Parser, Message, Clone)]
+#[command(name = "let")]
+pub struct LetCmd {
+    pub name: Option<String>,
+    /// Multiple arguments are joined with spaces.
+    #[arg(num_args = 1.., allow_hyphen_values = true)]
+    pub value: Vec<String>,
+}
+
+/// Removes session variables set with `let`.
+#[derive(Parser, Message, Clone)]
+#[command(name = "unset")]
+pub struct UnsetCmd {
+    #[arg(required = true)]
+    pub names: Vec<String>,
+}
+
+/// Whether `name` can be used as a session variable, i.e. `$name` would
+/// substitute it.
+fn is_variable_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn let_inner(input: In<CommandMsg<LetCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let mut session = world
+            .get_mut::<ConsoleSessionVars>(input.console_id)
+            .ok_or("Console has no session variables")?;
+        let LetCmd { name, value } = &input.command;
+        let Some(name) = name else {
+            let mut lines = session
+                .vars
+                .iter()
+                .map(|(name, value)| format!("{name} = {value}"))
+                .collect::<Vec<_>>();
+            if lines.is_empty() {
+                return Err("No variables are set".to_string());
+            }
+            lines.sort();
+            return Ok(lines.join("\n"));
+        };
+        if value.is_empty() {
+            return session
+                .vars
+                .get(name)
+                .map(|value| format!("{name} = {value}"))
+                .ok_or_else(|| format!("'{name}' is not set"));
+        }
+        if !is_variable_name(name) {
+            return Err(format!(
+                "Invalid variable name '{name}', use letters, digits and `_`"
+            ));
+        }
+        let value = value.join(" ");
+        session.vars.insert(name.clone(), value.clone());
+        Ok(format!("{name} = {value}"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn unset_inner(input: In<CommandMsg<UnsetCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let mut session = world
+            .get_mut::<ConsoleSessionVars>(input.console_id)
+            .ok_or("Console has no session variables")?;
+        // Nothing is unset unless every name is set.
+        let names = &input.command.names;
+        if let Some(name) = names.iter().find(|name| !session.vars.contains_key(*name)) {
+            return Err(format!("'{name}' is not set"));
+        }
+        let mut out = vec![];
+        for name in names.iter() {
+            if session.vars.remove(name).is_some() {
+                out.push(format!("Unset {name}"));
+            }
+        }
+        Ok(out.join("\n"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_let_msg(mut reader: MessageReader<CommandMsg<LetCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(let_inner, msg.clone());
+    }
+}
+
+fn on_unset_msg(mut reader: MessageReader<CommandMsg<UnsetCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(unset_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(PreUpdate, (on_let_msg, on_unset_msg));
+    app.add_console_command::<LetCmd>();
+    app.add_console_command::<UnsetCmd>();
+}
@@ -1,7 +1,26 @@
 use clap::Parser;
 use regex::Regex;
 
-use crate::prelude::*;
+use std::hash::BuildHasher;
+
+use bevy::{
+    asset::{LoadState, ReflectAsset},
+    ecs::{
+        entity_disabling::{Disabled, Internal},
+        event::EventKey,
+        query::{Allow, QueryFilter},
+        schedule::{NodeId, ScheduleGraph, Schedules, SystemKey, graph::Direction},
+        system::ScheduleSystem,
+    },
+    platform::hash::FixedHasher,
+    reflect::ReflectRef,
+};
+
+use super::{help::commands_table, state::console_states};
+use crate::{
+    commands::{lookup::*, reflection::*, table::console_extent},
+    prelude::*,
+};
 
 /// Provides static information about the world.
 #[derive(Parser, Message, Clone)]
@@ -9,6 +28,9 @@ use crate::prelude::*;
 pub struct ShowCmd {
     #[arg(required = true)]
     pub kind: ShowKind,
+    /// The schedule for `systems`, e.g. `Update`, or the asset type for
+    /// `assets`, e.g. `Image`.
+    pub target: Option<String>,
     /// Filter for the request. By default will act like `grep`, i.e.
     /// will match substrings. Pass `-e` to search as a regular expression.
     #[arg(short, long, global = true, required_if_eq("use_expression", "true"))]
@@ -16,8 +38,27 @@ pub struct ShowCmd {
     /// Pass this flag to interpret the filter as a regular expression.
     #[arg(short = 'e')]
     pub use_expression: bool,
+    /// Only print these columns, e.g. `--columns name,id`.
+    #[arg(short, long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+    /// Sort the rows by this column.
+    #[arg(short, long)]
+    pub sort: Option<String>,
+    /// Reverse the sort order.
+    #[arg(short, long, requires = "sort")]
+    pub reverse: bool,
+    /// Print a single page of rows which fits in the console, starting at 1.
+    #[arg(short, long)]
+    pub page: Option<usize>,
+    /// The output format. Structured formats omit the row count.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
 }
 
+/// What `show` lists.
+///
+/// There is no `plugins` kind: bevy keeps plugins on the [App], which commands
+/// can't reach, and the [World] has no record of them.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, clap::ValueEnum)]
 pub enum ShowKind {
     Commands,
@@ -25,127 +66,478 @@ pub enum ShowKind {
     Archetypes,
     Types,
     Resources,
+    /// Schedules and their named system sets, with set ordering.
+    /// The running schedule (e.g. `PreUpdate`) is not listed.
+    Schedules,
+    /// Systems in every schedule, or in the given schedule, in execution order.
+    Systems,
+    /// Current values of every [States] type registered with
+    /// `register_type_state` or `add_console_state`, and the queued values of
+    /// the latter.
+    States,
+    /// Asset types registered for reflection, or the assets of the given type.
+    Assets,
+    Observers,
+    /// Message types with pending and total counts. Counts are only known for
+    /// message types whose `Messages<T>` is registered for reflection.
+    Messages,
+    /// Entity counts.
+    Entities,
 }
 
-fn find_inner(input: In<CommandMsg<ShowCmd>>, world: &mut World) {
-    let mut vec: Vec<String> = match input.command.kind {
-        ShowKind::Commands => world
-            .resource::<ConsoleCommands>()
-            .values()
-            .map(|v| v.cmd.clone().render_usage().ansi().to_string())
-            .collect(),
-        ShowKind::Components => world
-            .components()
-            .iter_registered()
-            .map(|c| {
+fn mutability(mutable: bool) -> &'static str {
+    if mutable { "mutable" } else { "immutable" }
+}
+
+fn show_table(
+    world: &World,
+    kind: ShowKind,
+    target: Option<&str>,
+    console_id: Entity,
+) -> Result<ConsoleTable, String> {
+    Ok(match kind {
+        ShowKind::Commands => commands_table(world, console_id),
+        ShowKind::Components => {
+            let mut table = ConsoleTable::new(["name", "id", "mutability", "storage", "requires"])
+                .with_align(1, ColumnAlign::Right);
+            for c in world.components().iter_registered() {
                 let required = c
                     .required_components()
                     .iter_ids()
-                    .map(|c| {
-                        world
-                            .components()
-                            .iter_registered()
-                            .find(|i| i.id() == c)
-                            .unwrap()
-                            .name()
-                            .to_string()
-                            + ", "
-                    })
-                    .collect::<String>();
-                format!(
-                    "{} | {:?} | {} | {:?} | Requires ({})\n",
-                    c.name(),
-                    c.id(),
-                    if c.mutable() { "mutable" } else { "immutable" },
-                    c.storage_type(),
-                    required,
-                )
-            })
+                    .map(|c| component_name(world, c))
+                    .collect::<Vec<_>>();
+                table.push([
+                    c.name().to_string(),
+                    c.id().index().to_string(),
+                    mutability(c.mutable()).to_string(),
+                    format!("{:?}", c.storage_type()),
+                    required.join(", "),
+                ]);
+            }
+            table
+        }
+        ShowKind::Archetypes => {
+            let mut table = ConsoleTable::new(["id", "table", "entities", "components"])
+                .with_align(0, ColumnAlign::Right)
+                .with_align(1, ColumnAlign::Right)
+                .with_align(2, ColumnAlign::Right);
+            for a in world.archetypes().iter() {
+                let components = a
+                    .iter_components()
+                    .filter_map(|c| world.components().get_info(c))
+                    .map(|info| info.name().shortname().to_string())
+                    .collect::<Vec<_>>();
+                table.push([
+                    a.id().index().to_string(),
+                    a.table_id().as_usize().to_string(),
+                    a.len().to_string(),
+                    components.join(", "),
+                ]);
+            }
+            table
+        }
+        ShowKind::Types => {
+            let mut table = ConsoleTable::new(["type", "kind"]);
+            for t in world.resource::<AppTypeRegistry>().0.read().iter() {
+                table.push([
+                    t.type_info().type_path().to_string(),
+                    t.type_info().kind().to_string(),
+                ]);
+            }
+            table
+        }
+        ShowKind::Resources => {
+            let mut table =
+                ConsoleTable::new(["name", "id", "mutability"]).with_align(1, ColumnAlign::Right);
+            for (id, _data) in world.storages().resources.iter() {
+                let c = world.components().get_info(id).unwrap();
+                table.push([
+                    c.name().to_string(),
+                    c.id().index().to_string(),
+                    mutability(c.mutable()).to_string(),
+                ]);
+            }
+            table
+        }
+        ShowKind::Schedules => schedules_table(world),
+        ShowKind::Systems => systems_table(world, target)?,
+        ShowKind::States => states_table(world),
+        ShowKind::Assets => assets_table(world, target)?,
+        ShowKind::Observers => observers_table(world),
+        ShowKind::Messages => messages_table(world),
+        ShowKind::Entities => entities_table(world),
+    })
+}
+
+/// The systems of a schedule and their names, in execution order if the
+/// schedule has been initialized. Initialized schedules move their systems out
+/// of the graph.
+fn schedule_systems(schedule: &Schedule) -> Vec<(SystemKey, String)> {
+    let name = |system: &ScheduleSystem| system.name().shortname().to_string();
+    match schedule.systems() {
+        Ok(systems) => systems.map(|(key, system)| (key, name(system))).collect(),
+        Err(_) => schedule
+            .graph()
+            .systems
+            .iter()
+            .map(|(key, system, _)| (key, name(system)))
             .collect(),
+    }
+}
 
-        ShowKind::Archetypes => world
-            .archetypes()
+fn node_name(graph: &ScheduleGraph, systems: &[(SystemKey, String)], node: NodeId) -> String {
+    match node {
+        NodeId::System(key) => systems
             .iter()
-            .map(|a| {
-                let mut entity_table = a.entities_with_location().collect::<Vec<_>>();
-                entity_table.sort_by(|(_, l), (_, l2)| {
-                    l.archetype_row.index().cmp(&l2.archetype_row.index())
-                });
-                let component_table = a
-                    .iter_components()
-                    .map(|c| unsafe { world.components().get_info_unchecked(c) });
-                let table = world.storages().tables.get(a.table_id()).unwrap();
-                let component_header = component_table
-                    .clone()
-                    .fold("  row | entity".to_string(), |header, info| {
-                        format!("{header} | {}", info.name())
-                    });
-                let entity_table = entity_table
-                    .iter()
-                    .map(|(e, l)| {
-                        let row = component_table.clone().fold(String::new(), |row, info| {
-                            format!(
-                                "{row} | {1:^0$}",
-                                info.name().len(),
-                                if table.has_column(info.id()) { "x" } else { "" }
-                            )
-                        });
-                        format!("{:>4} | {:>6}{row}\n", l.archetype_row.index(), e)
-                    })
-                    .collect::<String>();
-                let header = format!(
-                    "Archetype {:?} :: {} entities, {} components",
-                    a.table_id(),
-                    a.entities().len(),
-                    a.component_count()
-                );
-                let separator = (0..header.len()).map(|_| '-').collect::<String>();
-                format!("{header}\n{separator}\n{component_header}\n{entity_table}",)
-            })
-            .collect::<Vec<String>>(),
-        ShowKind::Types => world
-            .resource::<AppTypeRegistry>()
-            .0
-            .read()
+            .find(|(other, _)| *other == key)
+            .map_or(format!("{key:?}"), |(_, name)| name.clone()),
+        NodeId::Set(key) if graph.system_sets[key].is_anonymous() => "(anonymous)".to_string(),
+        NodeId::Set(key) => format!("{:?}", &graph.system_sets[key]),
+    }
+}
+
+fn schedules_table(world: &World) -> ConsoleTable {
+    let mut table = ConsoleTable::new(["schedule", "systems", "set", "before", "after"])
+        .with_align(1, ColumnAlign::Right);
+    let Some(schedules) = world.get_resource::<Schedules>() else {
+        return table;
+    };
+    for (label, schedule) in schedules.iter() {
+        let graph = schedule.graph();
+        let label = format!("{label:?}");
+        let names = schedule_systems(schedule);
+        let systems = names.len().to_string();
+        let sets = graph
+            .system_sets
             .iter()
-            .map(|t| format!("{:?}", t.type_info()))
-            .collect(),
-        ShowKind::Resources => world
-            .storages()
-            .resources
+            .filter(|(_, set, _)| set.system_type().is_none() && !set.is_anonymous())
+            .map(|(key, ..)| NodeId::Set(key))
+            .collect::<Vec<_>>();
+        if sets.is_empty() {
+            table.push([label.clone(), systems.clone()]);
+        }
+        for node in sets {
+            let neighbors = |direction| {
+                graph
+                    .dependency()
+                    .graph()
+                    .neighbors_directed(node, direction)
+                    .map(|other| node_name(graph, &names, other))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            table.push([
+                label.clone(),
+                systems.clone(),
+                node_name(graph, &names, node),
+                neighbors(Direction::Outgoing),
+                neighbors(Direction::Incoming),
+            ]);
+        }
+    }
+    table
+}
+
+fn systems_table(world: &World, filter: Option<&str>) -> Result<ConsoleTable, String> {
+    let mut table = ConsoleTable::new(["schedule", "order", "system", "sets"])
+        .with_align(1, ColumnAlign::Right);
+    let Some(schedules) = world.get_resource::<Schedules>() else {
+        return Ok(table);
+    };
+    let mut found = false;
+    for (label, schedule) in schedules.iter() {
+        let label = format!("{label:?}");
+        if filter.is_some_and(|filter| filter != label) {
+            continue;
+        }
+        found = true;
+        let graph = schedule.graph();
+        let systems = schedule_systems(schedule);
+        // Uninitialized schedules have no execution order yet.
+        let initialized = schedule.systems().is_ok();
+        for (order, (key, name)) in systems.iter().enumerate() {
+            let sets = graph
+                .hierarchy()
+                .graph()
+                .neighbors_directed(NodeId::System(*key), Direction::Incoming)
+                .filter(|set| match set {
+                    NodeId::Set(key) => graph.system_sets[*key].system_type().is_none(),
+                    NodeId::System(_) => false,
+                })
+                .map(|set| node_name(graph, &systems, set))
+                .collect::<Vec<_>>();
+            table.push([
+                label.clone(),
+                if initialized {
+                    order.to_string()
+                } else {
+                    "-".to_string()
+                },
+                name.clone(),
+                sets.join(", "),
+            ]);
+        }
+    }
+    match filter {
+        Some(schedule) if !found => Err(format!(
+            "Schedule '{schedule}' not found, it may be the one currently running"
+        )),
+        _ => Ok(table),
+    }
+}
+
+/// States registered with `register_type_state` or `add_console_state`. Only
+/// the latter know their queued next value.
+fn states_table(world: &World) -> ConsoleTable {
+    let mut table = ConsoleTable::new(["state", "current", "next"]);
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let console_states = console_states(world);
+    let next = |type_path: &str| {
+        console_states
             .iter()
-            .map(|(id, _data)| {
-                let c = world.components().get_info(id).unwrap();
-                format!(
-                    "{} | {:?} | {} | {:?} \n",
-                    c.name(),
-                    c.id(),
-                    if c.mutable() { "mutable" } else { "immutable" },
-                    c.storage_type(),
-                )
-            })
-            .collect(),
+            .find(|(other, ..)| *other == type_path)
+            .and_then(|(.., next)| next.clone())
+            .unwrap_or("-".to_string())
     };
-    if let Some(filter) = input.command.filter.as_ref() {
-        vec = if input.command.use_expression {
-            vec.into_iter().filter(|val| val.contains(filter)).collect()
-        } else {
-            let expr = Regex::new(filter);
-            if let Err(e) = expr {
-                input.println(
-                    &mut world.commands(),
-                    format!("Failed to parse regular expression.\nError: {e:?}"),
-                );
-                return;
+    for registration in registry.iter() {
+        let Some(current) = registration
+            .data::<ReflectState>()
+            .and_then(|state| state.reflect(world))
+        else {
+            continue;
+        };
+        let type_path = registration.type_info().type_path_table();
+        table.push([
+            type_path.short_path().to_string(),
+            format_value_inline(current.as_partial_reflect(), &registry),
+            next(type_path.path()),
+        ]);
+    }
+    for (type_path, name, current, _) in console_states.iter() {
+        if registry
+            .get_with_type_path(type_path)
+            .is_some_and(|registration| registration.data::<ReflectState>().is_some())
+        {
+            continue;
+        }
+        let Some(current) = current else {
+            continue;
+        };
+        table.push([name.to_string(), current.clone(), next(type_path)]);
+    }
+    table
+}
+
+fn assets_table(world: &World, asset: Option<&str>) -> Result<ConsoleTable, String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let Some(asset) = asset else {
+        let mut table = ConsoleTable::new(["asset", "count"]).with_align(1, ColumnAlign::Right);
+        for registration in registry.iter() {
+            if let Some(reflect) = registration.data::<ReflectAsset>() {
+                table.push([
+                    registration.type_info().type_path().to_string(),
+                    reflect.len(world).to_string(),
+                ]);
+            }
+        }
+        return Ok(table);
+    };
+    let registration = find_registration(&registry, asset)?;
+    let reflect = registration.data::<ReflectAsset>().ok_or_else(|| {
+        format!(
+            "'{}' is not a reflected asset (missing register_asset_reflect)",
+            registration.type_info().type_path()
+        )
+    })?;
+    let server = world.get_resource::<AssetServer>();
+    let mut table = ConsoleTable::new(["id", "path", "load state"]);
+    for id in reflect.ids(world) {
+        let path = server.and_then(|server| server.get_path(id));
+        let state = server
+            .and_then(|server| server.get_load_state(id))
+            .map(|state| match state {
+                LoadState::Failed(e) => format!("failed: {e}"),
+                state => format!("{state:?}"),
+            });
+        table.push([
+            format!("{id:?}"),
+            path.map(|path| path.to_string()).unwrap_or_default(),
+            state.unwrap_or("-".to_string()),
+        ]);
+    }
+    Ok(table)
+}
+
+/// The name of an observed event. Bevy registers a component for every event
+/// type, whose [ComponentId] the [EventKey] wraps without exposing it. Both
+/// hash the same though, so the component is found by its hash, and the event
+/// is its generic argument.
+fn event_name(world: &World, key: &EventKey) -> String {
+    let hash = FixedHasher.hash_one(key);
+    world
+        .components()
+        .iter_registered()
+        .find(|info| FixedHasher.hash_one(info.id()) == hash)
+        .map(|info| {
+            let name = info.name().shortname().to_string();
+            name.split_once('<')
+                .and_then(|(_, event)| event.strip_suffix('>'))
+                .map_or(name.clone(), ToString::to_string)
+        })
+        .unwrap_or_else(|| format!("{key:?}"))
+}
+
+fn observers_table(world: &World) -> ConsoleTable {
+    let mut table = ConsoleTable::new(["entity", "system", "events", "watching"]);
+    let Some(mut query) = world.try_query_filtered::<(Entity, &Observer), Allow<Internal>>() else {
+        return table;
+    };
+    for (entity, observer) in query.iter(world) {
+        let descriptor = observer.descriptor();
+        let event_names = descriptor
+            .event_keys()
+            .iter()
+            .map(|key| event_name(world, key))
+            .collect::<Vec<_>>();
+        let watching = descriptor
+            .entities()
+            .iter()
+            .map(|entity| describe_entity(world, *entity))
+            .chain(descriptor.components().iter().map(|id| {
+                world
+                    .components()
+                    .get_info(*id)
+                    .map_or(format!("{id:?}"), |info| {
+                        info.name().shortname().to_string()
+                    })
+            }))
+            .collect::<Vec<_>>();
+        table.push([
+            entity.to_string(),
+            observer.system_name().shortname().to_string(),
+            event_names.join(", "),
+            watching.join(", "),
+        ]);
+    }
+    table
+}
+
+/// Message types, found by the name of their `Messages<T>` resource. The
+/// counts are read through reflection from `Messages<T>`'s fields, so they
+/// are only known if it is registered, e.g. with
+/// `app.register_type::<Messages<T>>()`, and the test below catches changes to
+/// its fields.
+fn messages_table(world: &World) -> ConsoleTable {
+    const MESSAGES: &str = "bevy_ecs::message::messages::Messages<";
+    let mut table = ConsoleTable::new(["message", "pending", "sent"])
+        .with_align(1, ColumnAlign::Right)
+        .with_align(2, ColumnAlign::Right);
+    let registry = world.resource::<AppTypeRegistry>().read();
+    for (id, _) in world.storages().resources.iter() {
+        let Some(info) = world.components().get_info(id) else {
+            continue;
+        };
+        let name = info.name().to_string();
+        let Some(message) = name
+            .strip_prefix(MESSAGES)
+            .and_then(|s| s.strip_suffix('>'))
+        else {
+            continue;
+        };
+        let value = info
+            .type_id()
+            .and_then(|type_id| registry.get_type_data::<ReflectResource>(type_id))
+            .and_then(|reflect| reflect.reflect(world).ok());
+        let count = |path: &str| {
+            let field = field_at(value?, path).ok()?;
+            match field.reflect_ref() {
+                ReflectRef::List(list) => Some(list.len()),
+                _ => field.try_downcast_ref::<usize>().copied(),
             }
-            let expr = expr.unwrap();
-            vec.into_iter().filter(|val| expr.is_match(val)).collect()
         };
+        let pending = count(".messages_a.messages")
+            .zip(count(".messages_b.messages"))
+            .map(|(a, b)| a + b);
+        table.push([
+            message.to_string(),
+            pending.map_or("-".to_string(), |n| n.to_string()),
+            count(".message_count").map_or("-".to_string(), |n| n.to_string()),
+        ]);
     }
-    let str = vec
-        .iter()
-        .fold(String::new(), |prev, next| format!("{prev}\n{next}"));
-    input.println(&mut world.commands(), str);
+    table
+}
+
+fn entities_table(world: &World) -> ConsoleTable {
+    fn count<F: QueryFilter>(world: &World) -> String {
+        world
+            .try_query_filtered::<(), F>()
+            .map_or(0, |mut query| query.iter(world).count())
+            .to_string()
+    }
+    let mut table = ConsoleTable::new(["entities", "count"]).with_align(1, ColumnAlign::Right);
+    table.push(["total".to_string(), world.entities().len().to_string()]);
+    table.push(["named".to_string(), count::<With<Name>>(world)]);
+    table.push(["roots".to_string(), count::<Without<ChildOf>>(world)]);
+    table.push(["ui nodes".to_string(), count::<With<Node>>(world)]);
+    table.push(["disabled".to_string(), count::<With<Disabled>>(world)]);
+    table.push(["internal".to_string(), count::<With<Internal>>(world)]);
+    table
+}
+
+fn find_inner(input: In<CommandMsg<ShowCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let cmd = &input.command;
+        let mut table = show_table(world, cmd.kind, cmd.target.as_deref(), input.console_id)?;
+        if let Some(filter) = cmd.filter.as_ref() {
+            let expr = if cmd.use_expression {
+                Some(
+                    Regex::new(filter)
+                        .map_err(|e| format!("Failed to parse regular expression.\nError: {e}"))?,
+                )
+            } else {
+                None
+            };
+            table.rows.retain(|row| {
+                let row = row.join(" | ");
+                match expr.as_ref() {
+                    Some(expr) => expr.is_match(&row),
+                    None => row.contains(filter.as_str()),
+                }
+            });
+        }
+        if let Some(column) = cmd.sort.as_ref() {
+            table.sort_by(column, cmd.reverse)?;
+        }
+        if !cmd.columns.is_empty() {
+            table.select(&cmd.columns)?;
+        }
+
+        let extent = console_extent(world, input.console_id);
+        let total = table.rows.len();
+        let mut footer = format!("{total} rows");
+        if let Some(page) = cmd.page {
+            // leave room for the echoed command, header, rule and footer
+            let page_size = extent.map_or(20, |(_, lines)| lines.saturating_sub(4).max(1));
+            let pages = total.div_ceil(page_size).max(1);
+            if page == 0 || page > pages {
+                return Err(format!("Page {page} is out of range 1..={pages}"));
+            }
+            table.rows = table
+                .rows
+                .into_iter()
+                .skip((page - 1) * page_size)
+                .take(page_size)
+                .collect();
+            footer = format!("page {page}/{pages} of {footer}");
+        }
+        let width = extent.map_or(usize::MAX, |(columns, _)| columns);
+        let body = table.render_as(cmd.format, width)?;
+        Ok(match cmd.format {
+            OutputFormat::Text => format!("{body}\n{footer}"),
+            _ => body,
+        })
+    })();
+    input.reply(&mut world.commands(), res);
 }
 
 fn on_find_msg(mut reader: MessageReader<CommandMsg<ShowCmd>>, mut commands: Commands) {
@@ -156,5 +548,55 @@ fn on_find_msg(mut reader: MessageReader<CommandMsg<ShowCmd>>, mut commands: Com
 
 pub fn plugin(app: &mut App) {
     app.add_systems(PreUpdate, on_find_msg);
-    app.add_console_command::<ShowCmd>();
+    app.add_console_command_at::<ShowCmd>(ConsoleLevel::Developer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConsolePlugin;
+
+    #[derive(Event, Message, Reflect)]
+    struct Ping;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ConsolePlugin));
+        app.add_message::<Ping>();
+        app.register_type::<Messages<Ping>>();
+        app.add_observer(|_: On<Ping>| {});
+        app.finish();
+        app.cleanup();
+        app.update();
+        app
+    }
+
+    fn row<'t>(table: &'t ConsoleTable, column: &str, value: &str) -> Option<&'t Vec<String>> {
+        let column = table.column(column).unwrap();
+        table.rows.iter().find(|row| row[column] == value)
+    }
+
+    #[test]
+    fn observers_name_their_events() {
+        let app = app();
+        let table = observers_table(app.world());
+        assert!(row(&table, "events", "Ping").is_some(), "{table:?}");
+        assert!(
+            row(&table, "events", "ConsolePrintln").is_some(),
+            "{table:?}"
+        );
+    }
+
+    #[test]
+    fn messages_count_registered_types() {
+        let mut app = app();
+        app.world_mut().write_message(Ping);
+        app.world_mut().write_message(Ping);
+        let table = messages_table(app.world());
+        let name = std::any::type_name::<Ping>();
+        assert_eq!(
+            row(&table, "message", name),
+            Some(&vec![name.to_string(), "2".to_string(), "2".to_string()])
+        );
+    }
 }
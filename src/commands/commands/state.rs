@@ -0,0 +1,217 @@
+use std::str::FromStr;
+
+use bevy::{
+    reflect::{TypeInfo, Typed},
+    state::state::{FreelyMutableState, StateTransitionEvent},
+};
+use clap::{Parser, Subcommand};
+
+use crate::prelude::*;
+
+/// Reads and changes [States] registered with `add_console_state`.
+///
+/// e.g. `state set GameState Paused`
+#[derive(Parser, Message, Clone)]
+#[command(name = "state")]
+pub struct StateCmd {
+    #[command(subcommand)]
+    pub action: StateAction,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum StateAction {
+    /// Prints the current value of a state, or of every state.
+    Get { state: Option<String> },
+    /// Queues a transition to the given variant.
+    Set { state: String, value: String },
+    /// Lists the variants of a state, or of every state.
+    List { state: Option<String> },
+}
+
+/// Type-erased access to a state registered with `add_console_state`.
+struct ConsoleState {
+    name: &'static str,
+    type_path: &'static str,
+    get: fn(&World) -> Option<String>,
+    /// The queued next value, if a transition is pending.
+    next: fn(&World) -> Option<String>,
+    set: fn(&mut World, &str, Entity) -> Result<(), String>,
+    variants: fn() -> Vec<&'static str>,
+}
+
+#[derive(Resource, Default)]
+struct ConsoleStates(Vec<ConsoleState>);
+impl ConsoleStates {
+    fn find(&self, name: &str) -> Result<&ConsoleState, String> {
+        self.0
+            .iter()
+            .find(|state| state.name.eq_ignore_ascii_case(name) || state.type_path == name)
+            .ok_or_else(|| format!("Unknown state '{name}'"))
+    }
+}
+
+/// Consoles waiting to hear about the transition of `S` to the value they
+/// queued.
+#[derive(Resource)]
+struct PendingTransition<S> {
+    consoles: Vec<(Entity, S)>,
+}
+impl<S> Default for PendingTransition<S> {
+    fn default() -> Self {
+        Self { consoles: vec![] }
+    }
+}
+
+fn variants<S: Typed>() -> Vec<&'static str> {
+    match S::type_info() {
+        TypeInfo::Enum(info) => info.variant_names().to_vec(),
+        _ => vec![],
+    }
+}
+
+fn get_state<S: States>(world: &World) -> Option<String> {
+    world
+        .get_resource::<State<S>>()
+        .map(|state| format!("{:?}", state.get()))
+}
+
+fn next_state<S: FreelyMutableState>(world: &World) -> Option<String> {
+    match world.get_resource::<NextState<S>>()? {
+        NextState::Pending(next) => Some(format!("{next:?}")),
+        NextState::Unchanged => None,
+    }
+}
+
+/// The type path, name, current and next value of every state registered
+/// with `add_console_state`.
+#[cfg(not(feature = "strip_dev_commands"))]
+pub(crate) fn console_states(
+    world: &World,
+) -> Vec<(&'static str, &'static str, Option<String>, Option<String>)> {
+    world
+        .get_resource::<ConsoleStates>()
+        .map_or(vec![], |states| {
+            states
+                .0
+                .iter()
+                .map(|state| {
+                    (
+                        state.type_path,
+                        state.name,
+                        (state.get)(world),
+                        (state.next)(world),
+                    )
+                })
+                .collect()
+        })
+}
+
+fn set_state<S: FreelyMutableState + FromStr>(
+    world: &mut World,
+    value: &str,
+    console_id: Entity,
+) -> Result<(), String> {
+    let value = S::from_str(value).map_err(|_| format!("Invalid value '{value}'"))?;
+    let mut next = world
+        .get_resource_mut::<NextState<S>>()
+        .ok_or("State has not been initialized")?;
+    next.set(value.clone());
+    // `NextState` holds a single value, so transitions queued before this one
+    // won't happen anymore.
+    let consoles = &mut world.resource_mut::<PendingTransition<S>>().consoles;
+    consoles.retain(|(_, queued)| *queued == value);
+    consoles.push((console_id, value));
+    Ok(())
+}
+
+/// Reports transitions requested from a console once they have happened,
+/// i.e. after `OnEnter` has run.
+fn report_transition<S: States + TypePath>(
+    mut reader: MessageReader<StateTransitionEvent<S>>,
+    mut pending: ResMut<PendingTransition<S>>,
+    mut commands: Commands,
+) {
+    for transition in reader.read() {
+        let Some(entered) = transition.entered.as_ref() else {
+            continue;
+        };
+        let exited = transition
+            .exited
+            .as_ref()
+            .map_or("None".to_string(), |exited| format!("{exited:?}"));
+        pending.consoles.retain(|(console_id, queued)| {
+            if queued != entered {
+                return true;
+            }
+            commands.trigger(ConsolePrintln {
+                message: format!(
+                    "{}: {exited} -> {entered:?}",
+                    S::type_ident().unwrap_or_default()
+                ),
+                console_id: *console_id,
+            });
+            false
+        });
+    }
+}
+
+fn state_inner(input: In<CommandMsg<StateCmd>>, world: &mut World) {
+    let res = world.resource_scope(|world, states: Mut<ConsoleStates>| {
+        let describe = |state: &ConsoleState| {
+            let current = (state.get)(world).unwrap_or("(not initialized)".to_string());
+            match (state.next)(world) {
+                Some(next) => format!("{}: {current} (next: {next})", state.name),
+                None => format!("{}: {current}", state.name),
+            }
+        };
+        let list =
+            |state: &ConsoleState| format!("{}: {}", state.name, (state.variants)().join(", "));
+        match &input.command.action {
+            StateAction::Get { state: Some(name) } => Ok(describe(states.find(name)?)),
+            StateAction::List { state: Some(name) } => Ok(list(states.find(name)?)),
+            StateAction::Get { state: None } => {
+                Ok(states.0.iter().map(describe).collect::<Vec<_>>().join("\n"))
+            }
+            StateAction::List { state: None } => {
+                Ok(states.0.iter().map(list).collect::<Vec<_>>().join("\n"))
+            }
+            StateAction::Set { state, value } => {
+                let state = states.find(state)?;
+                (state.set)(world, value, input.console_id).map_err(|e| {
+                    format!("{e}, expected one of: {}", (state.variants)().join(", "))
+                })?;
+                Ok(format!("Queued {}: {value}", state.name))
+            }
+        }
+    });
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_state_msg(mut reader: MessageReader<CommandMsg<StateCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(state_inner, msg.clone());
+    }
+}
+
+/// Makes `S` available to the `state` command, which is registered along with
+/// the first state.
+pub(crate) fn add_console_state<S: FreelyMutableState + FromStr + Typed>(app: &mut App) {
+    if !app.world().contains_resource::<ConsoleStates>() {
+        app.init_resource::<ConsoleStates>();
+        app.add_systems(PreUpdate, on_state_msg);
+        app.add_console_command_at::<StateCmd>(ConsoleLevel::Cheat);
+    }
+    app.init_resource::<PendingTransition<S>>();
+    app.add_systems(Update, report_transition::<S>);
+    app.world_mut()
+        .resource_mut::<ConsoleStates>()
+        .0
+        .push(ConsoleState {
+            name: S::type_ident().unwrap_or_default(),
+            type_path: S::type_path(),
+            get: get_state::<S>,
+            next: next_state::<S>,
+            set: set_state::<S>,
+            variants: variants::<S>,
+        });
+}
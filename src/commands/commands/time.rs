@@ -0,0 +1,146 @@
+use clap::{Parser, Subcommand};
+
+use crate::prelude::*;
+
+/// Controls [Time<Virtual>], e.g. `time pause`, `time step 10` or
+/// `time scale 0.25`.
+///
+/// Prints the elapsed and delta times and the fixed timestep when no action
+/// is given.
+#[derive(Parser, Message, Clone)]
+#[command(name = "time")]
+pub struct TimeCmd {
+    #[command(subcommand)]
+    pub action: Option<TimeAction>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum TimeAction {
+    /// Pauses virtual time.
+    Pause,
+    /// Resumes virtual time, cancelling any steps.
+    Resume,
+    /// Sets the relative speed of virtual time.
+    Scale { speed: f32 },
+    /// Pauses virtual time, then runs the given number of frames with virtual
+    /// time advancing before pausing again.
+    Step {
+        #[arg(default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        frames: u32,
+    },
+}
+
+/// Frames left to run for `time step`.
+#[derive(Resource, Debug, Default)]
+struct TimeSteps {
+    remaining: u32,
+    total: u32,
+    /// Whether virtual time was unpaused for the frame which just ran.
+    running: bool,
+    console_id: Option<Entity>,
+}
+
+fn describe_time(world: &World) -> String {
+    let time = world.resource::<Time<Virtual>>();
+    let mut out = format!(
+        "elapsed: {:.3}s\ndelta: {:.2?}\nspeed: {}x{}",
+        time.elapsed_secs_f64(),
+        time.delta(),
+        time.relative_speed(),
+        if time.is_paused() { " (paused)" } else { "" },
+    );
+    if let Some(real) = world.get_resource::<Time<Real>>() {
+        out += &format!("\nreal elapsed: {:.3}s", real.elapsed_secs_f64());
+    }
+    if let Some(fixed) = world.get_resource::<Time<Fixed>>() {
+        out += &format!("\nfixed timestep: {:.2?}", fixed.timestep());
+    }
+    out
+}
+
+fn time_inner(input: In<CommandMsg<TimeCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let Some(action) = input.command.action.as_ref() else {
+            return Ok(describe_time(world));
+        };
+        let mut steps = world.resource_mut::<TimeSteps>();
+        if !matches!(action, TimeAction::Scale { .. }) {
+            *steps = TimeSteps::default();
+        }
+        let mut time = world.resource_mut::<Time<Virtual>>();
+        match action {
+            TimeAction::Pause => {
+                time.pause();
+                Ok(format!("Paused at {:.3}s", time.elapsed_secs_f64()))
+            }
+            TimeAction::Resume => {
+                time.unpause();
+                Ok(format!("Resumed at {:.3}s", time.elapsed_secs_f64()))
+            }
+            TimeAction::Scale { speed } => {
+                if !speed.is_finite() || *speed < 0. {
+                    return Err(format!("Invalid speed {speed}"));
+                }
+                time.set_relative_speed(*speed);
+                Ok(format!("Speed: {speed}x"))
+            }
+            TimeAction::Step { frames } => {
+                time.pause();
+                *world.resource_mut::<TimeSteps>() = TimeSteps {
+                    remaining: *frames,
+                    total: *frames,
+                    running: false,
+                    console_id: Some(input.console_id),
+                };
+                Ok(format!("Stepping {}", ConsoleDelay::Frames(*frames)))
+            }
+        }
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+/// Unpauses virtual time for exactly as many frames as were requested with
+/// `time step`. Runs at the end of the frame, so the next update of
+/// [Time<Virtual>] sees the new state.
+fn step_time(
+    mut steps: ResMut<TimeSteps>,
+    mut time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+) {
+    if steps.running {
+        steps.remaining = steps.remaining.saturating_sub(1);
+    }
+    if steps.remaining > 0 {
+        time.unpause();
+        steps.running = true;
+    } else if steps.running {
+        time.pause();
+        steps.running = false;
+        if let Some(console_id) = steps.console_id.take() {
+            commands.trigger(ConsolePrintln {
+                message: format!(
+                    "Stepped {}, paused at {:.3}s",
+                    ConsoleDelay::Frames(steps.total),
+                    time.elapsed_secs_f64()
+                ),
+                console_id,
+            });
+        }
+    }
+}
+
+fn on_time_msg(mut reader: MessageReader<CommandMsg<TimeCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(time_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<TimeSteps>();
+    app.add_systems(PreUpdate, on_time_msg);
+    app.add_systems(
+        Last,
+        step_time.run_if(|steps: Res<TimeSteps>| steps.remaining > 0 || steps.running),
+    );
+    app.add_console_command_at::<TimeCmd>(ConsoleLevel::Cheat);
+}
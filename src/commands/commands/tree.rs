@@ -0,0 +1,202 @@
+use bevy::ecs::component::ComponentId;
+use clap::Parser;
+
+use crate::{commands::lookup::*, prelude::*};
+
+/// Prints the entity hierarchy as a tree.
+///
+/// e.g. `tree --ui --depth 2` or `tree Player --components`
+#[derive(Parser, Message, Clone)]
+#[command(name = "tree")]
+pub struct TreeCmd {
+    /// The root entity, as accepted by `inspect`. Prints every root entity by
+    /// default.
+    pub root: Option<String>,
+    /// Only print this many levels below the root.
+    #[arg(short, long)]
+    pub depth: Option<usize>,
+    /// Only print entities whose [Name] contains this, along with their
+    /// ancestors.
+    #[arg(short, long)]
+    pub name: Option<String>,
+    /// Only print entities with this component, along with their ancestors.
+    /// Can be repeated.
+    #[arg(short, long)]
+    pub with: Vec<String>,
+    /// List the components of every entity.
+    #[arg(short, long)]
+    pub components: bool,
+    /// Only start from UI root nodes.
+    #[arg(long)]
+    pub ui: bool,
+    /// The output format. Structured formats nest children in each entity.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+}
+
+struct TreePrinter<'a> {
+    world: &'a World,
+    cmd: &'a TreeCmd,
+    with: Vec<ComponentId>,
+    hidden: Vec<ComponentId>,
+    lines: Vec<String>,
+}
+impl TreePrinter<'_> {
+    fn matches(&self, entity: Entity) -> bool {
+        let entity_ref = self.world.entity(entity);
+        let name_matches = self.cmd.name.as_ref().is_none_or(|filter| {
+            entity_ref
+                .get::<Name>()
+                .is_some_and(|name| name.contains(filter.as_str()))
+        });
+        name_matches && self.with.iter().all(|id| entity_ref.contains_id(*id))
+    }
+
+    /// True if the entity or any of its descendants pass the filters.
+    fn keep(&self, entity: Entity) -> bool {
+        self.matches(entity) || self.children(entity).any(|child| self.keep(child))
+    }
+
+    fn children(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.world
+            .get::<Children>(entity)
+            .into_iter()
+            .flat_map(|children| children.iter())
+    }
+
+    fn components(&self, entity: Entity) -> Vec<String> {
+        self.world
+            .entity(entity)
+            .archetype()
+            .components()
+            .iter()
+            .filter(|id| !self.hidden.contains(id))
+            .filter_map(|id| self.world.components().get_info(*id))
+            .map(|info| info.name().shortname().to_string())
+            .collect()
+    }
+
+    fn label(&self, entity: Entity) -> String {
+        let mut label = describe_entity(self.world, entity);
+        if self.cmd.components {
+            label += &format!(" [{}]", self.components(entity).join(", "));
+        }
+        label
+    }
+
+    fn node(&self, entity: Entity, depth: usize) -> serde_json::Value {
+        let mut node = serde_json::json!({
+            "entity": entity.to_string(),
+            "name": self.world.get::<Name>(entity).map(|name| name.as_str()),
+        });
+        if self.cmd.components {
+            node["components"] = self.components(entity).into();
+        }
+        if self.cmd.depth.is_none_or(|max| depth < max) {
+            let children = self
+                .children(entity)
+                .filter(|child| self.keep(*child))
+                .map(|child| self.node(child, depth + 1))
+                .collect::<Vec<_>>();
+            node["children"] = children.into();
+        }
+        node
+    }
+
+    fn print(&mut self, entity: Entity, prefix: &str, connector: &str, depth: usize) {
+        let mut line = format!("{prefix}{connector}{}", self.label(entity));
+        let children = self
+            .children(entity)
+            .filter(|child| self.keep(*child))
+            .collect::<Vec<_>>();
+        if self.cmd.depth.is_some_and(|max| depth >= max) {
+            if !children.is_empty() {
+                line += &format!(" (+{} children)", children.len());
+            }
+            self.lines.push(line);
+            return;
+        }
+        self.lines.push(line);
+        let prefix = match connector {
+            "├── " => format!("{prefix}│   "),
+            "└── " => format!("{prefix}    "),
+            _ => prefix.to_string(),
+        };
+        for (i, child) in children.iter().enumerate() {
+            let connector = if i + 1 == children.len() {
+                "└── "
+            } else {
+                "├── "
+            };
+            self.print(*child, &prefix, connector, depth + 1);
+        }
+    }
+}
+
+fn tree_inner(input: In<CommandMsg<TreeCmd>>, world: &mut World) {
+    let res = (|| -> Result<String, String> {
+        let cmd = &input.command;
+        let roots = match cmd.root.as_ref() {
+            Some(root) => vec![resolve_entity(world, root)?],
+            None if cmd.ui => world
+                .query_filtered::<Entity, (With<Node>, Without<ChildOf>)>()
+                .iter(world)
+                .collect(),
+            None => world
+                .query_filtered::<Entity, Without<ChildOf>>()
+                .iter(world)
+                .collect(),
+        };
+        let with = cmd
+            .with
+            .iter()
+            .map(|name| find_component(world, name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let hidden = [
+            world.component_id::<ChildOf>(),
+            world.component_id::<Children>(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let mut printer = TreePrinter {
+            world,
+            cmd,
+            with,
+            hidden,
+            lines: vec![],
+        };
+        let mut roots = roots
+            .into_iter()
+            .filter(|root| printer.keep(*root))
+            .collect::<Vec<_>>();
+        roots.sort_by_key(|root| root.index());
+        if cmd.format != OutputFormat::Text {
+            let nodes = roots
+                .iter()
+                .map(|root| printer.node(*root, 0))
+                .collect::<Vec<_>>();
+            return cmd.format.serialize(&nodes);
+        }
+        for root in roots {
+            printer.print(root, "", "", 0);
+        }
+        if printer.lines.is_empty() {
+            return Err("No entities match".to_string());
+        }
+        Ok(printer.lines.join("\n"))
+    })();
+    input.reply(&mut world.commands(), res);
+}
+
+fn on_tree_msg(mut reader: MessageReader<CommandMsg<TreeCmd>>, mut commands: Commands) {
+    for msg in reader.read() {
+        commands.run_system_cached_with(tree_inner, msg.clone());
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(PreUpdate, on_tree_msg);
+    app.add_console_command_at::<TreeCmd>(ConsoleLevel::Developer);
+}
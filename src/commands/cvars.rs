@@ -0,0 +1,256 @@
+use std::{any::TypeId, fmt::Debug, ops::RangeInclusive};
+
+use bevy::{
+    platform::collections::HashMap,
+    reflect::{FromReflect, GetPath, GetTypeRegistration, PartialReflect, TypeInfo, Typed},
+};
+
+use crate::{commands::reflection::*, prelude::*};
+
+/// Types which can be stored in a [ConsoleVar].
+pub trait ConsoleVarValue: Reflect + FromReflect + TypePath + GetTypeRegistration + Clone {}
+impl<T> ConsoleVarValue for T where T: Reflect + FromReflect + TypePath + GetTypeRegistration + Clone
+{}
+
+/// Written when a console variable is changed from the console with
+/// `cvar set`, `cvar reset` or the `<name> <value>` shorthand.
+///
+/// Changes made through [ConsoleVars::set] are only visible through change
+/// detection on [ConsoleVars]. Variables bound to resource fields don't send
+/// this message, use change detection on the resource instead.
+#[derive(Message, Debug, Clone)]
+pub struct CvarChanged<T: Send + Sync + 'static> {
+    pub name: String,
+    pub value: T,
+}
+
+/// Where the value of a [ConsoleVar] is stored.
+pub(crate) enum CvarSource {
+    /// In the variable itself.
+    Owned(Box<dyn PartialReflect>),
+    /// In a field of a reflected resource, see
+    /// [CommandExt::expose_resource_to_console].
+    Resource { type_id: TypeId, path: String },
+}
+
+/// A typed value which can be read and set from the console.
+///
+/// Register one with [CommandExt::add_console_var].
+pub struct ConsoleVar {
+    pub description: String,
+    /// The level a console needs to set or reset the variable. Variables
+    /// added with [CommandExt::add_console_var] can be set by every console,
+    /// resource fields need [ConsoleLevel::Cheat] like `set` does.
+    pub level: ConsoleLevel,
+    pub(crate) source: CvarSource,
+    /// The type of the value.
+    pub(crate) type_id: TypeId,
+    pub(crate) default: Box<dyn PartialReflect>,
+    /// The allowed range, formatted for display.
+    pub(crate) range: Option<String>,
+    validate: Box<dyn Fn(&dyn PartialReflect) -> Result<(), String> + Send + Sync>,
+    notify: Option<fn(&mut World, &str, &dyn PartialReflect)>,
+}
+impl Debug for ConsoleVar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsoleVar")
+            .field("description", &self.description)
+            .field("level", &self.level)
+            .field("default", &self.default)
+            .field("range", &self.range)
+            .finish()
+    }
+}
+impl ConsoleVar {
+    pub(crate) fn new<T: ConsoleVarValue>(default: T, description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            level: ConsoleLevel::User,
+            source: CvarSource::Owned(Box::new(default.clone())),
+            type_id: TypeId::of::<T>(),
+            default: Box::new(default),
+            range: None,
+            validate: Box::new(|value| {
+                T::from_reflect(value)
+                    .map(|_| ())
+                    .ok_or_else(|| format!("Expected a {}", T::type_path()))
+            }),
+            notify: Some(|world, name, value| {
+                if let Some(value) = T::from_reflect(value) {
+                    world.write_message(CvarChanged {
+                        name: name.to_string(),
+                        value,
+                    });
+                }
+            }),
+        }
+    }
+    pub(crate) fn with_range<T: ConsoleVarValue + PartialOrd + Debug>(
+        mut self,
+        range: RangeInclusive<T>,
+    ) -> Self {
+        self.range = Some(format!("{range:?}"));
+        self.validate = Box::new(move |value| {
+            let value =
+                T::from_reflect(value).ok_or_else(|| format!("Expected a {}", T::type_path()))?;
+            if range.contains(&value) {
+                Ok(())
+            } else {
+                Err(format!("{value:?} is outside of {range:?}"))
+            }
+        });
+        self
+    }
+    /// The variables for every field of `R`, named `<resource>.<field>` where
+    /// `<resource>` is the snake case name of `R`. Resources which aren't
+    /// structs are exposed as a single variable.
+    pub(crate) fn resource_fields<R: Resource + Reflect + Typed + Default>() -> Vec<(String, Self)>
+    {
+        let prefix = snake_case(R::type_ident().unwrap_or_default());
+        let resource = R::default();
+        let fields = match R::type_info() {
+            TypeInfo::Struct(info) => info
+                .iter()
+                .map(|field| {
+                    (
+                        format!("{prefix}.{}", field.name()),
+                        format!(".{}", field.name()),
+                    )
+                })
+                .collect(),
+            _ => vec![(prefix, String::new())],
+        };
+        fields
+            .into_iter()
+            .filter_map(|(name, path)| {
+                let default = match path.as_str() {
+                    "" => resource.as_partial_reflect(),
+                    path => resource.reflect_path(path).ok()?,
+                };
+                let type_info = default.get_represented_type_info()?;
+                let (type_id, type_path) = (type_info.type_id(), type_info.type_path());
+                let var = Self {
+                    description: format!(
+                        "{} in {}",
+                        type_info.ty().short_path(),
+                        R::short_type_path()
+                    ),
+                    level: ConsoleLevel::Cheat,
+                    source: CvarSource::Resource {
+                        type_id: TypeId::of::<R>(),
+                        path,
+                    },
+                    type_id,
+                    default: default
+                        .reflect_clone()
+                        .map(PartialReflect::into_partial_reflect)
+                        .unwrap_or_else(|_| default.to_dynamic()),
+                    range: None,
+                    validate: Box::new(move |value| match value.get_represented_type_info() {
+                        Some(info) if info.type_id() == type_id => Ok(()),
+                        _ => Err(format!("Expected a {type_path}")),
+                    }),
+                    notify: None,
+                };
+                Some((name, var))
+            })
+            .collect()
+    }
+    /// The value of the variable, or `None` if it is bound to a resource field.
+    pub fn value(&self) -> Option<&dyn PartialReflect> {
+        match &self.source {
+            CvarSource::Owned(value) => Some(value.as_ref()),
+            CvarSource::Resource { .. } => None,
+        }
+    }
+    pub fn default_value(&self) -> &dyn PartialReflect {
+        self.default.as_ref()
+    }
+    /// Reads the value, wherever it is stored.
+    pub(crate) fn read<'w>(&'w self, world: &'w World) -> Result<&'w dyn PartialReflect, String> {
+        match &self.source {
+            CvarSource::Owned(value) => Ok(value.as_ref()),
+            CvarSource::Resource { type_id, path } => {
+                let reflect = world
+                    .resource::<AppTypeRegistry>()
+                    .read()
+                    .get_type_data::<ReflectResource>(*type_id)
+                    .cloned()
+                    .ok_or("Resource is not reflected")?;
+                let value = reflect.reflect(world).map_err(|e| e.to_string())?;
+                field_at(value, path)
+            }
+        }
+    }
+    /// Checks the value against the type and range of the variable, then
+    /// applies it. Owned variables also write a [CvarChanged] message.
+    pub(crate) fn write(
+        &mut self,
+        world: &mut World,
+        name: &str,
+        value: &dyn PartialReflect,
+    ) -> Result<(), String> {
+        (self.validate)(value)?;
+        match &mut self.source {
+            CvarSource::Owned(current) => current.try_apply(value).map_err(|e| e.to_string())?,
+            CvarSource::Resource { type_id, path } => {
+                let reflect = world
+                    .resource::<AppTypeRegistry>()
+                    .read()
+                    .get_type_data::<ReflectResource>(*type_id)
+                    .cloned()
+                    .ok_or("Resource is not reflected")?;
+                let mut resource = reflect
+                    .reflect_mut(&mut *world)
+                    .map_err(|e| e.to_string())?;
+                field_at_mut(&mut *resource, path)?
+                    .try_apply(value)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        if let Some(notify) = self.notify {
+            notify(world, name, value);
+        }
+        Ok(())
+    }
+}
+
+fn snake_case(ident: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in ident.char_indices() {
+        if c.is_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Every registered [ConsoleVar], by name.
+#[derive(Resource, Debug, Default, Deref, DerefMut)]
+pub struct ConsoleVars(HashMap<String, ConsoleVar>);
+impl ConsoleVars {
+    pub fn var(&self, name: &str) -> Option<&ConsoleVar> {
+        self.0.get(name)
+    }
+    /// Returns the value of a variable if it exists and has type `T`.
+    /// Variables bound to resource fields always return `None`.
+    pub fn get<T: Reflect>(&self, name: &str) -> Option<&T> {
+        self.0.get(name)?.value()?.try_downcast_ref::<T>()
+    }
+    /// Sets a variable from code, checking its type and range.
+    /// Fails for variables bound to resource fields.
+    pub fn set<T: Reflect>(&mut self, name: &str, value: T) -> Result<(), String> {
+        let var = self
+            .0
+            .get_mut(name)
+            .ok_or_else(|| format!("Unknown variable '{name}'"))?;
+        (var.validate)(&value)?;
+        match &mut var.source {
+            CvarSource::Owned(current) => current.try_apply(&value).map_err(|e| e.to_string()),
+            CvarSource::Resource { .. } => Err(format!(
+                "'{name}' is bound to a resource field, set the resource instead"
+            )),
+        }
+    }
+}
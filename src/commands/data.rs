@@ -1,3 +1,6 @@
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use super::scripts::ConsoleScriptLocation;
 use crate::prelude::*;
 use bevy::{ecs::system::SystemId, platform::collections::HashMap};
 
@@ -11,17 +14,236 @@ pub struct CallCommandEvent {
 #[reflect(Resource)]
 pub struct ConsoleCommands(HashMap<String, ConcreteConsoleCommand>);
 
+/// One-shot systems which can be run by name with the `run` command.
+///
+/// See [CommandExt::register_console_system].
+#[derive(Resource, Debug, Default, Deref, DerefMut)]
+pub struct ConsoleSystems(HashMap<String, SystemId>);
+
+/// A command line bound to a key combination with `bind`.
+#[derive(Debug, Clone)]
+pub struct ConsoleBinding {
+    /// The key combination as it was typed.
+    pub keys: String,
+    pub command: String,
+    /// The console the binding was made in, which the command runs in.
+    pub console_id: Entity,
+}
+impl ConsoleBinding {
+    /// The `bind` command which recreates this binding.
+    pub fn to_command(&self) -> String {
+        format!(
+            "bind {} {}",
+            self.keys,
+            shlex::try_quote(&self.command).unwrap_or_default()
+        )
+    }
+}
+
+/// Every key binding, by the [ConsoleAction] which triggers it.
+///
+/// See the `bind` command.
+#[derive(Resource, Debug, Default, Deref, DerefMut)]
+pub struct ConsoleBindings(HashMap<ConsoleAction, ConsoleBinding>);
+
+/// A number of frames or an amount of virtual time, written as `10`,
+/// `500ms`, `2s` or `1.5m`. Used by `after`, `every` and `wait`.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum ConsoleDelay {
+    Frames(u32),
+    Time(Duration),
+}
+impl ConsoleDelay {
+    /// Counts down by one frame or by the virtual time delta, and returns true
+    /// once nothing is left. Neither counts down while virtual time is paused.
+    pub fn tick(&mut self, time: &Time<Virtual>) -> bool {
+        match self {
+            _ if time.is_paused() => {}
+            Self::Frames(frames) => *frames = frames.saturating_sub(1),
+            Self::Time(duration) => *duration = duration.saturating_sub(time.delta()),
+        }
+        match self {
+            Self::Frames(frames) => *frames == 0,
+            Self::Time(duration) => duration.is_zero(),
+        }
+    }
+}
+impl FromStr for ConsoleDelay {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        if let Ok(frames) = s.parse() {
+            return Ok(Self::Frames(frames));
+        }
+        let split = s.find(|c: char| c.is_alphabetic()).unwrap_or(s.len());
+        let (value, unit) = s.split_at(split);
+        let value = value
+            .parse::<f32>()
+            .ok()
+            .filter(|value| *value >= 0.)
+            .ok_or_else(|| format!("Invalid delay '{s}'"))?;
+        let secs = match unit {
+            "ms" => value / 1000.,
+            "s" => value,
+            "m" => value * 60.,
+            _ => return Err(format!("Invalid delay '{s}', expected frames, ms, s or m")),
+        };
+        Ok(Self::Time(Duration::from_secs_f32(secs)))
+    }
+}
+impl Display for ConsoleDelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Frames(1) => write!(f, "1 frame"),
+            Self::Frames(frames) => write!(f, "{frames} frames"),
+            Self::Time(duration) => write!(f, "{duration:.2?}"),
+        }
+    }
+}
+
+/// A command line scheduled with `after` or `every`.
+#[derive(Debug, Clone)]
+pub struct ConsoleJob {
+    pub id: u32,
+    pub console_id: Entity,
+    pub command: String,
+    pub delay: ConsoleDelay,
+    pub remaining: ConsoleDelay,
+    pub repeat: bool,
+}
+
+/// Every running [ConsoleJob]. See the `jobs` command.
+#[derive(Resource, Debug, Default)]
+pub struct ConsoleJobs {
+    next_id: u32,
+    pub jobs: Vec<ConsoleJob>,
+}
+impl ConsoleJobs {
+    /// Schedules a command line and returns the id of its job.
+    pub fn add(
+        &mut self,
+        console_id: Entity,
+        command: String,
+        delay: ConsoleDelay,
+        repeat: bool,
+    ) -> u32 {
+        self.next_id += 1;
+        self.jobs.push(ConsoleJob {
+            id: self.next_id,
+            console_id,
+            command,
+            delay,
+            remaining: delay,
+            repeat,
+        });
+        self.next_id
+    }
+}
+
+/// Restricts which commands a [Console] can run.
+///
+/// Consoles without this component can run every registered command and
+/// builtin. Entries match command names exactly, or by prefix when they end in
+/// `*`, so `"dev_*"` allows every command whose name starts with `dev_`.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+pub struct ConsoleCommandScope {
+    pub allowed: Vec<String>,
+}
+impl ConsoleCommandScope {
+    pub fn new(allowed: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed: allowed.into_iter().map(Into::into).collect(),
+        }
+    }
+    pub fn allows(&self, name: &str) -> bool {
+        self.allowed
+            .iter()
+            .any(|entry| match entry.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => entry == name,
+            })
+    }
+}
+
+/// How privileged a command is. Levels are ordered, so a console at
+/// [ConsoleLevel::Cheat] can also run [ConsoleLevel::User] commands.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Reflect,
+    strum::Display,
+    strum::EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum ConsoleLevel {
+    /// Safe for players, such as `help`, `bind` or variables added with
+    /// `add_console_var`.
+    #[default]
+    User,
+    /// Changes the game, such as `spawn`, `time` or resource fields exposed
+    /// as console variables.
+    Cheat,
+    /// Inspects the world, such as `show`, `get` or `query`. Not registered
+    /// at all with the `strip_dev_commands` feature.
+    Developer,
+}
+
+/// Limits the [ConsoleLevel] of commands a [Console] can run.
+///
+/// Consoles without this component can run commands of every level, so
+/// release builds should add e.g. `ConsolePermissions::new(ConsoleLevel::User)`
+/// and raise it once cheats are enabled.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+pub struct ConsolePermissions {
+    pub level: ConsoleLevel,
+}
+impl ConsolePermissions {
+    pub fn new(level: ConsoleLevel) -> Self {
+        Self { level }
+    }
+    pub fn allows(&self, level: ConsoleLevel) -> bool {
+        level <= self.level
+    }
+    /// The error printed when `name` needs a higher level than this console's.
+    pub(crate) fn refusal(&self, name: &str, level: ConsoleLevel) -> String {
+        format!(
+            "'{name}' needs the {level} permission level, this console is {}",
+            self.level
+        )
+    }
+}
+
+/// The last entity clicked outside of a [Console], used by `inspect picked`.
+#[derive(Resource, Debug, Default, Clone, Copy, Deref, DerefMut, Reflect)]
+#[reflect(Resource)]
+pub struct ConsolePickedEntity(pub Option<Entity>);
+
 #[derive(Debug, Clone, strum::EnumIter, strum::Display)]
 pub enum ConsoleBuiltin {
     #[strum(serialize = "clear")]
     Clear,
 }
+impl ConsoleBuiltin {
+    /// The description listed by `help`.
+    pub fn about(&self) -> &'static str {
+        match self {
+            ConsoleBuiltin::Clear => "Clears the console",
+        }
+    }
+}
 
 #[derive(Debug, Clone, Reflect)]
 #[reflect(opaque)]
 pub struct ConcreteConsoleCommand {
     pub cmd: clap::Command,
     pub dispatch: SystemId<In<CallCommandEvent>>,
+    pub level: ConsoleLevel,
 }
 
 #[derive(Debug, Clone, Message)]
@@ -36,4 +258,94 @@ impl<T: ConsoleCommand> CommandMsg<T> {
             console_id: self.console_id,
         })
     }
+    /// Prints the result of the command, and sets the console's `$?` to 1 if
+    /// it failed. Errors of script lines are prefixed with the `file:line`.
+    pub fn reply(&self, commands: &mut Commands, res: Result<String, String>) {
+        match res {
+            Ok(message) => self.println(commands, message),
+            Err(e) => {
+                set_console_status(commands, self.console_id, 1);
+                let console_id = self.console_id;
+                commands.queue(move |world: &mut World| {
+                    let message = match world.get::<ConsoleScriptLocation>(console_id) {
+                        Some(location) => format!("{}: {e}", **location),
+                        None => e,
+                    };
+                    world.trigger(ConsolePrintln {
+                        message,
+                        console_id,
+                    });
+                });
+            }
+        }
+    }
+}
+
+/// Sets [ConsoleSessionVars::status] once commands are applied.
+pub(crate) fn set_console_status(commands: &mut Commands, console_id: Entity, status: i32) {
+    commands.queue(move |world: &mut World| {
+        if let Some(mut vars) = world.get_mut::<ConsoleSessionVars>(console_id) {
+            vars.status = status;
+        }
+    });
+}
+
+/// Session variables of a [Console], set with `let <name> <value>` and
+/// substituted into command lines as `$name` or `${name}`.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+pub struct ConsoleSessionVars {
+    pub vars: HashMap<String, String>,
+    /// The status of the last command, substituted as `$?`. 0 on success,
+    /// 1 if the command failed, 2 if its arguments could not be parsed, 126 if
+    /// the console's [ConsolePermissions] do not allow it and 127 if it does
+    /// not exist.
+    pub status: i32,
+}
+impl ConsoleSessionVars {
+    /// Replaces `$name`, `${name}` and `$?` everywhere but inside single
+    /// quotes or after a `\`. Unset variables are replaced with nothing, like
+    /// in a shell.
+    pub fn substitute(&self, line: &str) -> String {
+        let mut out = String::with_capacity(line.len());
+        let mut chars = line.chars().peekable();
+        let (mut single, mut double) = (false, false);
+        let is_name = |c: &char| c.is_alphanumeric() || *c == '_';
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if !single => {
+                    out.push(c);
+                    out.extend(chars.next());
+                }
+                '\'' if !double => {
+                    single = !single;
+                    out.push(c);
+                }
+                '"' if !single => {
+                    double = !double;
+                    out.push(c);
+                }
+                '$' if !single => match chars.peek() {
+                    Some('?') => {
+                        chars.next();
+                        out += &self.status.to_string();
+                    }
+                    Some('{') => {
+                        chars.next();
+                        let name = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+                        out += self.vars.get(&name).map_or("", String::as_str);
+                    }
+                    Some(c) if is_name(c) && !c.is_ascii_digit() => {
+                        let mut name = String::new();
+                        while let Some(c) = chars.next_if(is_name) {
+                            name.push(c);
+                        }
+                        out += self.vars.get(&name).map_or("", String::as_str);
+                    }
+                    _ => out.push(c),
+                },
+                _ => out.push(c),
+            }
+        }
+        out
+    }
 }
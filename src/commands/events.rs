@@ -1,25 +1,90 @@
 use strum::IntoEnumIterator;
 
+use super::{
+    commands::cvar::{CvarAction, CvarCmd},
+    scripts::ConsoleScriptLocation,
+};
 use crate::prelude::*;
 
 fn on_call_console_command(
     trigger: On<CallCommandEvent>,
     cmds: Res<ConsoleCommands>,
+    vars: Res<ConsoleVars>,
     mut commands: Commands,
-    console: Query<&Console>,
+    mut console: Query<(
+        &Console,
+        &mut ConsoleSessionVars,
+        Option<&ConsoleCommandScope>,
+        Option<&ConsolePermissions>,
+        Option<&ConsoleScriptLocation>,
+    )>,
 ) {
-    let split = trigger.command_name.split(" ").collect::<Vec<_>>();
-    let name = r!(split.first());
-    let console = console.get(trigger.console_id).unwrap();
+    let (console, mut session, scope, permissions, location) =
+        console.get_mut(trigger.console_id).unwrap();
     commands.trigger(ConsolePrintln {
-        message: format!("{}{}", console.prompt, trigger.command_name),
+        message: console.format_input(&trigger.command_name),
         console_id: trigger.console_id,
     });
-    if let Some(cmd) = cmds.get(*name) {
-        commands.run_system_with(cmd.dispatch, trigger.event().clone());
-    } else if let Some(cmd) = ConsoleBuiltin::iter().find(|b| b.to_string() == *name) {
+    // `$name` is substituted before the line is split into arguments.
+    let line = session.substitute(&trigger.command_name);
+    session.status = 0;
+    let name = r!(line.split_whitespace().next());
+    if scope.is_some_and(|scope| !scope.allows(name)) {
+        session.status = 127;
+        commands.trigger(ConsolePrintln {
+            message: format!("Unknown command '{name}'"),
+            console_id: trigger.console_id,
+        });
+    } else if let Some(cmd) = cmds.get(name)
+        && let Some(permissions) = permissions
+        && !permissions.allows(cmd.level)
+    {
+        session.status = 126;
+        commands.trigger(ConsolePrintln {
+            message: format!(
+                "{}{}",
+                location.map_or(String::new(), |location| format!("{}: ", **location)),
+                permissions.refusal(name, cmd.level)
+            ),
+            console_id: trigger.console_id,
+        });
+    } else if let Some(cmd) = cmds.get(name) {
+        commands.run_system_with(
+            cmd.dispatch,
+            CallCommandEvent {
+                command_name: line.clone(),
+                console_id: trigger.console_id,
+            },
+        );
+    } else if let Some(cmd) = ConsoleBuiltin::iter().find(|b| b.to_string() == name) {
         commands.run_system_cached_with(builtins, (cmd, trigger.console_id));
+    } else if vars.contains_key(name) {
+        // `<name>` reads a variable and `<name> <value>` sets it.
+        let Some(split) = shlex::split(&line) else {
+            session.status = 2;
+            commands.trigger(ConsolePrintln {
+                message: "Invalid quoting".to_string(),
+                console_id: trigger.console_id,
+            });
+            return;
+        };
+        let value = split.get(1..).unwrap_or_default().to_vec();
+        let action = if value.is_empty() {
+            CvarAction::Get {
+                name: name.to_string(),
+            }
+        } else {
+            CvarAction::Set {
+                name: name.to_string(),
+                value,
+            }
+        };
+        commands.write_message(CommandMsg {
+            console_id: trigger.console_id,
+            command: CvarCmd { action },
+        });
     } else {
+        session.status = 127;
         commands.trigger(ConsolePrintln {
             message: format!("Unknown command '{name}'"),
             console_id: trigger.console_id,
@@ -0,0 +1,114 @@
+use crate::prelude::*;
+#[cfg(not(feature = "strip_dev_commands"))]
+use bevy::ecs::component::ComponentId;
+use bevy::{
+    ecs::entity::EntityRow,
+    reflect::{TypeRegistration, TypeRegistry},
+};
+
+/// Parses an entity from its index, e.g. `12`, or its index and generation
+/// as displayed by bevy, e.g. `12v0`.
+pub(crate) fn parse_entity(world: &World, token: &str) -> Option<Entity> {
+    let (index, generation) = match token.split_once('v') {
+        Some((index, generation)) => (index, Some(generation)),
+        None => (token, None),
+    };
+    let row = EntityRow::from_raw_u32(index.parse().ok()?)?;
+    let entity = world.entities().resolve_from_id(row)?;
+    if generation.is_some_and(|generation| generation != entity.generation().to_string()) {
+        return None;
+    }
+    world.entities().contains(entity).then_some(entity)
+}
+
+/// Resolves an entity from its index (see [parse_entity]), its [Name], or
+/// `picked` for the [ConsolePickedEntity].
+pub(crate) fn resolve_entity(world: &mut World, token: &str) -> Result<Entity, String> {
+    if let Some(entity) = parse_entity(world, token) {
+        return Ok(entity);
+    }
+    if token == "picked" {
+        return world
+            .get_resource::<ConsolePickedEntity>()
+            .and_then(|picked| picked.0)
+            .filter(|entity| world.entities().contains(*entity))
+            .ok_or("No entity has been picked".to_string());
+    }
+    let named = world
+        .query::<(Entity, &Name)>()
+        .iter(world)
+        .filter(|(_, name)| name.as_str() == token)
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    match named.as_slice() {
+        [entity] => Ok(*entity),
+        [] => Err(format!("No entity matches '{token}'")),
+        _ => Err(format!(
+            "'{token}' is ambiguous, it names {}",
+            named
+                .iter()
+                .map(Entity::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Formats an entity with its [Name], e.g. `12v0 "Player"`.
+#[cfg(not(feature = "strip_dev_commands"))]
+pub(crate) fn describe_entity(world: &World, entity: Entity) -> String {
+    match world.get::<Name>(entity) {
+        Some(name) => format!("{entity} \"{name}\""),
+        None => entity.to_string(),
+    }
+}
+
+/// Looks up a registered type by its full or short type path.
+pub(crate) fn find_registration<'r>(
+    registry: &'r TypeRegistry,
+    name: &str,
+) -> Result<&'r TypeRegistration, String> {
+    if let Some(registration) = registry.get_with_type_path(name) {
+        return Ok(registration);
+    }
+    if registry.is_ambiguous(name) {
+        return Err(format!(
+            "'{name}' is ambiguous, use the full type path instead"
+        ));
+    }
+    registry
+        .get_with_short_type_path(name)
+        .ok_or_else(|| format!("Type '{name}' is not registered"))
+}
+
+/// The display name of a component, as listed by `show components`.
+#[cfg(not(feature = "strip_dev_commands"))]
+pub(crate) fn component_name(world: &World, id: ComponentId) -> String {
+    world
+        .components()
+        .get_info(id)
+        .map(|info| info.name().to_string())
+        .unwrap_or_else(|| format!("{id:?}"))
+}
+
+/// Looks up a component by its full or short name.
+#[cfg(not(feature = "strip_dev_commands"))]
+pub(crate) fn find_component(world: &World, name: &str) -> Result<ComponentId, String> {
+    let mut short_matches = vec![];
+    for info in world.components().iter_registered() {
+        let full = info.name();
+        if full.to_string() == name {
+            return Ok(info.id());
+        }
+        if full.shortname().to_string() == name {
+            short_matches.push(info.id());
+        }
+    }
+    match short_matches.as_slice() {
+        [id] => Ok(*id),
+        [] => Err(format!("Component '{name}' is not registered")),
+        _ => Err(format!(
+            "'{name}' is ambiguous, use the full type path instead"
+        )),
+    }
+}
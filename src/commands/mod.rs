@@ -3,15 +3,29 @@ use crate::prelude::*;
 mod app_ext;
 #[allow(clippy::module_inception)]
 mod commands;
+mod cvars;
 mod data;
 mod events;
+mod lookup;
+mod output;
+mod reflection;
+mod scripts;
+mod table;
 
 pub mod prelude {
     pub use super::app_ext::*;
+    pub use super::cvars::*;
     pub use super::data::*;
+    pub use super::output::OutputFormat;
+    pub use super::scripts::{ConsoleScript, ConsoleScriptLoader, ScriptLine};
+    pub use super::table::{ColumnAlign, ConsoleTable};
 }
 
 pub fn plugin(app: &mut App) {
     app.init_resource::<ConsoleCommands>();
-    app.add_plugins((events::plugin, commands::plugin));
+    app.add_plugins((events::plugin, scripts::plugin, commands::plugin));
+}
+
+pub fn finish(app: &mut App) {
+    scripts::finish(app);
 }
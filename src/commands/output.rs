@@ -0,0 +1,32 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How inspection commands print their results, selected with `--format`.
+///
+/// `text` is meant for reading in the console, the other formats for tools
+/// which scrape console output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Ron,
+    /// Comma-separated values. Only supported by tabular output.
+    Csv,
+}
+impl OutputFormat {
+    /// Serializes structured output as pretty JSON or RON.
+    pub fn serialize<T: Serialize + ?Sized>(self, value: &T) -> Result<String, String> {
+        match self {
+            OutputFormat::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+            OutputFormat::Ron => {
+                let config = ron::ser::PrettyConfig::default().indentor("  ".to_string());
+                ron::ser::to_string_pretty(value, config).map_err(|e| e.to_string())
+            }
+            OutputFormat::Text | OutputFormat::Csv => Err(format!(
+                "--format {} is not supported by this command",
+                self.to_possible_value().unwrap().get_name()
+            )),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{commands::lookup::find_registration, prelude::*};
+use bevy::reflect::{
+    GetPath, PartialReflect, TypeRegistration, TypeRegistry,
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+};
+use serde::de::DeserializeSeed;
+
+/// Looks up a type which can be used as a component through reflection.
+pub(crate) fn component_registration<'r>(
+    registry: &'r TypeRegistry,
+    name: &str,
+) -> Result<(&'r TypeRegistration, &'r ReflectComponent), String> {
+    let registration = find_registration(registry, name)?;
+    let reflect = registration.data::<ReflectComponent>().ok_or_else(|| {
+        format!(
+            "'{}' is not a reflected component (missing #[reflect(Component)])",
+            registration.type_info().type_path()
+        )
+    })?;
+    Ok((registration, reflect))
+}
+
+/// Builds a component value from RON, or from its [ReflectDefault] when no
+/// value is given.
+pub(crate) fn build_value(
+    registration: &TypeRegistration,
+    value: Option<&str>,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn PartialReflect>, String> {
+    match value {
+        Some(value) => parse_value(value, registration, registry),
+        None => registration
+            .data::<ReflectDefault>()
+            .map(|default| default.default().into_partial_reflect())
+            .ok_or_else(|| {
+                format!(
+                    "'{}' has no default (missing #[reflect(Default)]), provide a value",
+                    registration.type_info().type_path()
+                )
+            }),
+    }
+}
+
+/// Splits `Type.field.path` into the type name and the reflect path.
+/// The path starts at the first `.` or `[` outside of generic brackets.
+pub(crate) fn split_path(input: &str) -> (&str, &str) {
+    let mut depth = 0usize;
+    for (i, c) in input.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            '.' | '[' if depth == 0 => return input.split_at(i),
+            _ => {}
+        }
+    }
+    (input, "")
+}
+
+/// Follows a reflect path, e.g. `.translation.x`.
+pub(crate) fn field_at<'a>(
+    value: &'a dyn Reflect,
+    path: &str,
+) -> Result<&'a dyn PartialReflect, String> {
+    if path.is_empty() {
+        return Ok(value.as_partial_reflect());
+    }
+    value.reflect_path(path).map_err(|e| e.to_string())
+}
+
+/// Mutable version of [field_at].
+pub(crate) fn field_at_mut<'a>(
+    value: &'a mut dyn Reflect,
+    path: &str,
+) -> Result<&'a mut dyn PartialReflect, String> {
+    if path.is_empty() {
+        return Ok(value.as_partial_reflect_mut());
+    }
+    value.reflect_path_mut(path).map_err(|e| e.to_string())
+}
+
+/// Pretty-prints a value as RON, falling back to its debug representation.
+pub(crate) fn format_value(value: &dyn PartialReflect, registry: &TypeRegistry) -> String {
+    let serializer = TypedReflectSerializer::new(value, registry);
+    let config = ron::ser::PrettyConfig::default().indentor("  ".to_string());
+    ron::ser::to_string_pretty(&serializer, config).unwrap_or_else(|_| format!("{value:?}"))
+}
+
+/// Prints a value as single-line RON, falling back to its debug representation.
+pub(crate) fn format_value_inline(value: &dyn PartialReflect, registry: &TypeRegistry) -> String {
+    let serializer = TypedReflectSerializer::new(value, registry);
+    ron::ser::to_string(&serializer).unwrap_or_else(|_| format!("{value:?}"))
+}
+
+/// Converts a value to JSON through its reflected serializer, falling back to
+/// its debug representation.
+#[cfg(not(feature = "strip_dev_commands"))]
+pub(crate) fn value_to_json(
+    value: &dyn PartialReflect,
+    registry: &TypeRegistry,
+) -> serde_json::Value {
+    let serializer = TypedReflectSerializer::new(value, registry);
+    serde_json::to_value(&serializer).unwrap_or_else(|_| format!("{value:?}").into())
+}
+
+/// Parses a RON value of the given type.
+pub(crate) fn parse_value(
+    input: &str,
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn PartialReflect>, String> {
+    let type_path = registration.type_info().type_path();
+    let mut deserializer = ron::Deserializer::from_str(input)
+        .map_err(|e| format!("Failed to parse {type_path}: {e}"))?;
+    TypedReflectDeserializer::new(registration, registry)
+        .deserialize(&mut deserializer)
+        .map_err(|e| format!("Failed to parse {type_path}: {e}"))
+}
+
+/// Parses a RON value with the same type as `target`, then applies it.
+pub(crate) fn set_value(
+    target: &mut dyn PartialReflect,
+    input: &str,
+    registry: &TypeRegistry,
+) -> Result<(), String> {
+    let info = target
+        .get_represented_type_info()
+        .ok_or("Target has no type information")?;
+    let registration = registry
+        .get(info.type_id())
+        .ok_or_else(|| format!("Type '{}' is not registered", info.type_path()))?;
+    let value = parse_value(input, registration, registry)?;
+    target.try_apply(value.as_ref()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,368 @@
+use std::{collections::VecDeque, path::Path};
+
+use bevy::{
+    asset::{
+        AssetLoader, LoadContext, LoadState,
+        io::{AssetSourceId, Reader},
+    },
+    tasks::block_on,
+};
+use strum::IntoEnumIterator;
+
+use crate::prelude::*;
+
+/// A console script loaded from a `.cfg` or `.console` file, run with
+/// `exec <path>` or [CommandExt::add_console_autoexec].
+///
+/// Every line is a command. Lines starting with `#` are comments, so a `#`
+/// elsewhere is kept, e.g. in `set color #ff0000`. A trailing `\` continues
+/// the command on the next line.
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct ConsoleScript {
+    pub lines: Vec<ScriptLine>,
+}
+
+/// A command in a [ConsoleScript].
+#[derive(Debug, Clone)]
+pub struct ScriptLine {
+    /// 1-based line the command starts on.
+    pub number: usize,
+    pub command: String,
+}
+
+impl ConsoleScript {
+    pub fn parse(source: &str) -> Self {
+        let mut lines = vec![];
+        let mut pending: Option<ScriptLine> = None;
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            let line = if line.starts_with('#') { "" } else { line };
+            let (line, continues) = match line.strip_suffix('\\') {
+                Some(line) => (line.trim_end(), true),
+                None => (line, false),
+            };
+            let current = pending.get_or_insert_with(|| ScriptLine {
+                number: i + 1,
+                command: String::new(),
+            });
+            if !line.is_empty() {
+                if !current.command.is_empty() {
+                    current.command.push(' ');
+                }
+                current.command.push_str(line);
+            }
+            if !continues {
+                lines.extend(pending.take().filter(|line| !line.command.is_empty()));
+            }
+        }
+        lines.extend(pending.filter(|line| !line.command.is_empty()));
+        Self { lines }
+    }
+}
+
+#[derive(Default)]
+pub struct ConsoleScriptLoader;
+impl AssetLoader for ConsoleScriptLoader {
+    type Asset = ConsoleScript;
+    type Settings = ();
+    type Error = BevyError;
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<ConsoleScript, BevyError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ConsoleScript::parse(&String::from_utf8(bytes)?))
+    }
+    fn extensions(&self) -> &[&str] {
+        &["cfg", "console"]
+    }
+}
+
+/// Writes a script to the default asset source, so it can be run with `exec`.
+///
+/// The path must be relative, stay inside the assets folder and end in `.cfg`
+/// or `.console`.
+pub(crate) fn save_script(world: &World, path: &str, source: &str) -> Result<(), String> {
+    let relative = Path::new(path);
+    if !relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)))
+    {
+        return Err(format!("{path} must be relative to the assets folder"));
+    }
+    if !relative
+        .extension()
+        .is_some_and(|ext| ConsoleScriptLoader.extensions().iter().any(|e| ext == *e))
+    {
+        return Err(format!("{path} must end in .cfg or .console"));
+    }
+    let writer = world
+        .get_resource::<AssetServer>()
+        .ok_or("Saving scripts needs the AssetPlugin")?
+        .get_source(AssetSourceId::Default)
+        .map_err(|e| e.to_string())?
+        .writer()
+        .map_err(|e| e.to_string())?;
+    block_on(writer.write_bytes(relative, source.as_bytes()))
+        .map_err(|e| format!("Failed to write {path}: {e}"))
+}
+
+/// The `file:line` of the script line a console last ran, which prefixes the
+/// errors of that command. It is removed when the script moves on.
+#[derive(Component, Deref)]
+pub(crate) struct ConsoleScriptLocation(String);
+
+/// A request to run a script once it has loaded.
+struct ScriptRequest {
+    path: String,
+    handle: Option<Handle<ConsoleScript>>,
+    /// The console the script runs in, or the first console if `None`.
+    console_id: Option<Entity>,
+    /// Runs the script again whenever the file changes.
+    watch: bool,
+    started: bool,
+}
+
+/// A script whose commands are being dispatched.
+struct RunningScript {
+    path: String,
+    console_id: Entity,
+    lines: VecDeque<ScriptLine>,
+    /// Set by `wait`, the script resumes once this has counted down.
+    wait: Option<ConsoleDelay>,
+}
+
+/// Scripts requested with `exec` or [CommandExt::add_console_autoexec].
+#[derive(Resource, Default)]
+pub(crate) struct ConsoleScripts {
+    requests: Vec<ScriptRequest>,
+    running: Vec<RunningScript>,
+}
+impl ConsoleScripts {
+    pub(crate) fn exec(
+        &mut self,
+        path: impl Into<String>,
+        console_id: Option<Entity>,
+        watch: bool,
+    ) {
+        self.requests.push(ScriptRequest {
+            path: path.into(),
+            handle: None,
+            console_id,
+            watch,
+            started: false,
+        });
+    }
+}
+
+/// Starts requested scripts once they have loaded, and watched scripts again
+/// when they change.
+fn start_scripts(
+    mut scripts: ResMut<ConsoleScripts>,
+    mut reader: MessageReader<AssetEvent<ConsoleScript>>,
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<ConsoleScript>>,
+    console_q: Query<Entity, With<Console>>,
+    mut commands: Commands,
+) {
+    let modified = reader
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let Some(first_console) = console_q.iter().min_by_key(|e| e.index()) else {
+        return;
+    };
+    let ConsoleScripts { requests, running } = &mut *scripts;
+    requests.retain_mut(|request| {
+        let console_id = request.console_id.unwrap_or(first_console);
+        let handle = request
+            .handle
+            .get_or_insert_with(|| asset_server.load(&request.path));
+        if let LoadState::Failed(e) = asset_server.load_state(handle.id()) {
+            commands.trigger(ConsolePrintln {
+                message: format!("Failed to load {}: {e}", request.path),
+                console_id,
+            });
+            return false;
+        }
+        let Some(script) = assets.get(handle.id()) else {
+            return true;
+        };
+        if !request.started || modified.contains(&handle.id()) {
+            request.started = true;
+            running.push(RunningScript {
+                path: request.path.clone(),
+                console_id,
+                lines: script.lines.iter().cloned().collect(),
+                wait: None,
+            });
+        }
+        request.watch
+    });
+}
+
+/// Checks that a script line names a known command and parses, so errors can
+/// be reported with the file and line.
+fn check_line(line: &str, cmds: &ConsoleCommands, vars: &ConsoleVars) -> Result<(), String> {
+    let split = shlex::split(line).ok_or("Invalid quoting")?;
+    let name = split.first().map(String::as_str).unwrap_or_default();
+    if let Some(cmd) = cmds.get(name) {
+        cmd.cmd
+            .clone()
+            .try_get_matches_from(&split[1..])
+            .map_err(|e| e.to_string().trim_end().to_string())?;
+        Ok(())
+    } else if ConsoleBuiltin::iter().any(|b| b.to_string() == name) || vars.contains_key(name) {
+        Ok(())
+    } else {
+        Err(format!("Unknown command '{name}'"))
+    }
+}
+
+/// Dispatches the next line of every running script through
+/// [CallCommandEvent].
+///
+/// Lines are dispatched a frame apart, after every other schedule has run,
+/// so each command has taken effect before the next line is checked and
+/// `$name` is substituted. `wait` lines suspend the script instead.
+fn run_scripts(
+    mut scripts: ResMut<ConsoleScripts>,
+    cmds: Res<ConsoleCommands>,
+    vars: Res<ConsoleVars>,
+    time: Res<Time<Virtual>>,
+    console_q: Query<&ConsoleSessionVars, With<Console>>,
+    location_q: Query<Entity, With<ConsoleScriptLocation>>,
+    mut commands: Commands,
+) {
+    for console_id in location_q.iter() {
+        commands
+            .entity(console_id)
+            .remove::<ConsoleScriptLocation>();
+    }
+    scripts.running.retain_mut(|script| {
+        let Ok(session) = console_q.get(script.console_id) else {
+            return false;
+        };
+        if let Some(wait) = script.wait.as_mut() {
+            if !wait.tick(&time) {
+                return true;
+            }
+            script.wait = None;
+        }
+        // Lines which fail the check are reported and skipped right away.
+        while let Some(line) = script.lines.pop_front() {
+            let command = session.substitute(&line.command);
+            let res = match command.split_whitespace().collect::<Vec<_>>().as_slice() {
+                ["wait", delay] => delay.parse().map(|delay| {
+                    script.wait = Some(delay);
+                }),
+                _ => check_line(&command, &cmds, &vars),
+            };
+            match res {
+                Ok(()) if script.wait.is_some() => break,
+                Ok(()) => {
+                    commands
+                        .entity(script.console_id)
+                        .insert(ConsoleScriptLocation(format!(
+                            "{}:{}",
+                            script.path, line.number
+                        )));
+                    commands.trigger(CallCommandEvent {
+                        command_name: line.command,
+                        console_id: script.console_id,
+                    });
+                    break;
+                }
+                Err(e) => commands.trigger(ConsolePrintln {
+                    message: format!("{}:{}: {e}", script.path, line.number),
+                    console_id: script.console_id,
+                }),
+            }
+        }
+        !script.lines.is_empty() || script.wait.is_some()
+    });
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleScripts>();
+    app.add_systems(
+        Last,
+        (start_scripts, run_scripts)
+            .chain()
+            .run_if(resource_exists::<Assets<ConsoleScript>>),
+    );
+}
+
+/// Scripts need the [AssetPlugin], which may be added after the
+/// [ConsolePlugin](crate::ConsolePlugin), so the asset is registered once
+/// every plugin has been built.
+pub fn finish(app: &mut App) {
+    if app.is_plugin_added::<AssetPlugin>() {
+        app.init_asset::<ConsoleScript>();
+        app.init_asset_loader::<ConsoleScriptLoader>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConsolePlugin;
+
+    fn commands(script: &ConsoleScript) -> Vec<(usize, &str)> {
+        script
+            .lines
+            .iter()
+            .map(|line| (line.number, line.command.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn only_leading_hashes_are_comments() {
+        let script = ConsoleScript::parse("# comment\n  # indented\nset color #ff0000\n");
+        assert_eq!(commands(&script), [(3, "set color #ff0000")]);
+    }
+
+    #[test]
+    fn continued_lines_start_at_their_first_line() {
+        let script = ConsoleScript::parse("spawn \\\n  Visibility \\\n  Transform\nclear");
+        assert_eq!(
+            commands(&script),
+            [(1, "spawn Visibility Transform"), (4, "clear")]
+        );
+    }
+
+    #[test]
+    fn runtime_errors_report_the_line() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), ConsolePlugin));
+        app.finish();
+        app.cleanup();
+        let console_id = app.world_mut().spawn(Console::default()).id();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<ConsoleScript>>()
+            .add(ConsoleScript::parse("# comment\nset Missing 1\nlet a 1"));
+        app.world_mut()
+            .resource_mut::<ConsoleScripts>()
+            .requests
+            .push(ScriptRequest {
+                path: "test.cfg".to_string(),
+                handle: Some(handle),
+                console_id: Some(console_id),
+                watch: false,
+                started: false,
+            });
+        for _ in 0..5 {
+            app.update();
+        }
+        let buffer = &app.world().get::<Console>(console_id).unwrap().buffer;
+        assert!(buffer.contains("test.cfg:2: "), "{buffer}");
+        assert!(!buffer.contains("test.cfg:3: "), "{buffer}");
+    }
+}
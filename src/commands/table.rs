@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+
+use crate::prelude::*;
+
+/// How cells in a [ConsoleTable] column are padded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColumnAlign {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Column-aligned text table for command output.
+///
+/// Cells which would overflow the available width are truncated with `…`,
+/// shrinking the widest columns first.
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleTable {
+    pub headers: Vec<String>,
+    pub align: Vec<ColumnAlign>,
+    pub rows: Vec<Vec<String>>,
+}
+impl ConsoleTable {
+    pub fn new(headers: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let headers = headers.into_iter().map(Into::into).collect::<Vec<_>>();
+        Self {
+            align: vec![ColumnAlign::Left; headers.len()],
+            headers,
+            rows: vec![],
+        }
+    }
+    /// Sets the alignment of the column at `index`.
+    pub fn with_align(mut self, index: usize, align: ColumnAlign) -> Self {
+        if let Some(column) = self.align.get_mut(index) {
+            *column = align;
+        }
+        self
+    }
+    /// Adds a row. Newlines in cells are replaced with spaces, and missing
+    /// cells are left empty.
+    pub fn push(&mut self, row: impl IntoIterator<Item = impl Into<String>>) {
+        let mut row = row
+            .into_iter()
+            .map(|cell| cell.into().replace('\n', " "))
+            .collect::<Vec<_>>();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+    }
+    /// Finds a column by its case-insensitive header.
+    pub fn column(&self, header: &str) -> Result<usize, String> {
+        self.headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(header))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown column '{header}', expected one of: {}",
+                    self.headers.join(", ")
+                )
+            })
+    }
+    /// Keeps only the given columns, in the given order.
+    pub fn select(&mut self, headers: &[String]) -> Result<(), String> {
+        let indices = headers
+            .iter()
+            .map(|header| self.column(header))
+            .collect::<Result<Vec<_>, _>>()?;
+        let pick = |values: &[String]| indices.iter().map(|i| values[*i].clone()).collect();
+        self.headers = pick(&self.headers);
+        self.align = indices.iter().map(|i| self.align[*i]).collect();
+        self.rows = self.rows.iter().map(|row| pick(row)).collect();
+        Ok(())
+    }
+    /// Sorts the rows by a column. Cells which both parse as numbers are
+    /// compared numerically.
+    pub fn sort_by(&mut self, header: &str, reverse: bool) -> Result<(), String> {
+        let index = self.column(header)?;
+        self.rows.sort_by(|a, b| {
+            let ordering = match (a[index].parse::<f64>(), b[index].parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                _ => a[index].cmp(&b[index]),
+            };
+            if reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        Ok(())
+    }
+    /// Renders the table, truncating cells so that no line is wider than
+    /// `max_width` characters where possible.
+    pub fn render(&self, max_width: usize) -> String {
+        const SEPARATOR: &str = " | ";
+        const MIN_WIDTH: usize = 3;
+        let mut widths = self
+            .headers
+            .iter()
+            .map(|header| header.chars().count())
+            .collect::<Vec<_>>();
+        for row in self.rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let separators = SEPARATOR.len() * widths.len().saturating_sub(1);
+        while widths.iter().sum::<usize>() + separators > max_width {
+            let Some(widest) = widths.iter_mut().max().filter(|width| **width > MIN_WIDTH) else {
+                break;
+            };
+            *widest -= 1;
+        }
+
+        let line = |cells: &[String]| {
+            cells
+                .iter()
+                .zip(widths.iter().zip(self.align.iter()))
+                .map(|(cell, (width, align))| {
+                    let cell = truncate(cell, *width);
+                    match align {
+                        ColumnAlign::Left => format!("{cell:<width$}"),
+                        ColumnAlign::Right => format!("{cell:>width$}"),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(SEPARATOR)
+                .trim_end()
+                .to_string()
+        };
+        let rule = widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-");
+        std::iter::once(line(&self.headers))
+            .chain(std::iter::once(rule))
+            .chain(self.rows.iter().map(|row| line(row)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    /// Each row as a map from header to cell. Cells which parse as numbers
+    /// are emitted as numbers.
+    pub fn records(&self) -> Vec<serde_json::Map<String, serde_json::Value>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                self.headers
+                    .iter()
+                    .zip(row)
+                    .map(|(header, cell)| {
+                        let value = cell
+                            .parse::<i64>()
+                            .map(serde_json::Value::from)
+                            .unwrap_or_else(|_| cell.clone().into());
+                        (header.clone(), value)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    /// Renders the table as RFC 4180 CSV.
+    pub fn to_csv(&self) -> String {
+        fn escape(cell: &str) -> String {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        }
+        std::iter::once(&self.headers)
+            .chain(self.rows.iter())
+            .map(|row| {
+                row.iter()
+                    .map(|cell| escape(cell))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    /// Renders the table in any [OutputFormat], see [Self::render].
+    pub fn render_as(&self, format: OutputFormat, max_width: usize) -> Result<String, String> {
+        match format {
+            OutputFormat::Text => Ok(self.render(max_width)),
+            OutputFormat::Csv => Ok(self.to_csv()),
+            format => format.serialize(&self.records()),
+        }
+    }
+}
+
+fn truncate(cell: &str, width: usize) -> String {
+    if cell.chars().count() <= width {
+        return cell.to_string();
+    }
+    let mut cell = cell
+        .chars()
+        .take(width.saturating_sub(1))
+        .collect::<String>();
+    cell.push('…');
+    cell
+}
+
+/// The number of characters and lines which fit in a console, if it has
+/// been laid out.
+pub(crate) fn console_extent(world: &World, console_id: Entity) -> Option<(usize, usize)> {
+    let node = world.get::<ComputedNode>(console_id)?;
+    let settings = world.get::<ConsoleUiSettings>(console_id)?;
+    let view = world.get::<ConsoleBufferView>(console_id)?;
+    let columns = settings.visible_columns(node.size().x * node.inverse_scale_factor());
+    (columns > 0 && view.range > 0).then_some((columns, view.range))
+}
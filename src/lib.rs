@@ -1,12 +1,15 @@
 mod actions;
 mod commands;
+mod stdin;
 mod ui;
 
 pub use actions::actions::public::*;
+pub use stdin::StdinConsolePlugin;
 
 pub mod prelude {
     pub use super::actions::prelude::*;
     pub use super::commands::prelude::*;
+    pub use super::stdin::StdinConsole;
     pub use super::ui::prelude::*;
     pub(crate) use bevy::prelude::*;
     pub(crate) use tiny_bail::prelude::*;
@@ -19,4 +22,7 @@ impl Plugin for ConsolePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((ui::plugin, commands::plugin, actions::plugin));
     }
+    fn finish(&self, app: &mut App) {
+        commands::finish(app);
+    }
 }
@@ -0,0 +1,234 @@
+use std::{
+    io::{BufRead, Write},
+    sync::{
+        Mutex,
+        mpsc::{self, Receiver, TryRecvError},
+    },
+};
+
+use crate::prelude::*;
+
+/// Runs commands read from stdin and prints their output to stdout, for
+/// dedicated servers and other apps without a window, e.g.
+/// `app.add_plugins((MinimalPlugins, ConsolePlugin, StdinConsolePlugin::default()))`.
+///
+/// Lines are run in a hidden [Console] marked with [StdinConsole], so every
+/// registered command works like it does in the UI. Input can also be piped,
+/// e.g. `echo time | my_server`.
+#[derive(Debug, Clone, Default)]
+pub struct StdinConsolePlugin {
+    /// Exits the app once stdin is closed and every line has run, which is
+    /// handy with piped input.
+    pub exit_on_eof: bool,
+}
+impl Plugin for StdinConsolePlugin {
+    fn build(&self, app: &mut App) {
+        add_stdin_console(
+            app,
+            spawn_reader(),
+            Box::new(std::io::stdout()),
+            self.exit_on_eof,
+        );
+    }
+}
+
+/// Marks the [Console] which runs commands from stdin, see
+/// [StdinConsolePlugin].
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+pub struct StdinConsole;
+
+/// Lines read from stdin by a background thread, and where output goes.
+#[derive(Resource)]
+struct StdinIo {
+    receiver: Mutex<Receiver<String>>,
+    output: Mutex<Box<dyn Write + Send>>,
+    exit_on_eof: bool,
+}
+
+fn add_stdin_console(
+    app: &mut App,
+    receiver: Receiver<String>,
+    output: Box<dyn Write + Send>,
+    exit_on_eof: bool,
+) {
+    app.insert_resource(StdinIo {
+        receiver: Mutex::new(receiver),
+        output: Mutex::new(output),
+        exit_on_eof,
+    });
+    app.add_systems(Startup, |mut commands: Commands| {
+        commands.spawn((Console::default(), StdinConsole, Visibility::Hidden));
+    });
+    app.add_systems(Last, read_stdin);
+    app.add_observer(print_stdout);
+}
+
+/// Reads stdin until it closes, which disconnects the returned receiver.
+fn spawn_reader() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name("stdin console".to_string())
+        .spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Couldn't read stdin: {e}");
+    }
+    receiver
+}
+
+/// Runs the next line from stdin through [CallCommandEvent].
+///
+/// Like scripts, one command runs per frame, after every other schedule, so
+/// piped commands take effect in order. Lines with unbalanced quotes or a
+/// trailing `\` are continued on the next line.
+fn read_stdin(
+    io: Res<StdinIo>,
+    mut console_q: Query<(Entity, &mut Console), With<StdinConsole>>,
+    mut commands: Commands,
+) {
+    let Ok((console_id, mut console)) = console_q.single_mut() else {
+        return;
+    };
+    let receiver = r!(io.receiver.lock());
+    loop {
+        let eof = match receiver.try_recv() {
+            Ok(line) => {
+                console.insert(&line);
+                if console.needs_continuation() {
+                    console.insert("\n");
+                    continue;
+                }
+                false
+            }
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => true,
+        };
+        let input = console.take_input();
+        if !input.trim().is_empty() {
+            commands.trigger(CallCommandEvent {
+                command_name: input.clone(),
+                console_id,
+            });
+            console.history.push(input);
+            return;
+        }
+        if eof {
+            if io.exit_on_eof {
+                commands.write_message(AppExit::Success);
+            }
+            return;
+        }
+    }
+}
+
+/// Prints output of the [StdinConsole] to stdout. Its buffer is cleared as
+/// nothing displays it.
+fn print_stdout(
+    trigger: On<ConsolePrintln>,
+    io: Res<StdinIo>,
+    mut console_q: Query<&mut Console, With<StdinConsole>>,
+) {
+    if let Ok(mut console) = console_q.get_mut(trigger.console_id) {
+        // Nothing can be done about a closed stdout.
+        let _ = writeln!(r!(io.output.lock()), "{}", trigger.message);
+        console.buffer.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, mpsc::Sender};
+
+    use super::*;
+    use crate::ConsolePlugin;
+
+    /// Output which can be read back after it was moved into the app.
+    #[derive(Clone, Default)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    impl SharedOutput {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn app(exit_on_eof: bool) -> (App, Sender<String>, SharedOutput) {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ConsolePlugin));
+        app.add_console_var("sv_name", "local".to_string(), "server name");
+        let (sender, receiver) = mpsc::channel();
+        let output = SharedOutput::default();
+        add_stdin_console(&mut app, receiver, Box::new(output.clone()), exit_on_eof);
+        app.finish();
+        app.cleanup();
+        (app, sender, output)
+    }
+
+    /// Updates until the app wants to exit, or gives up after a while.
+    fn run(app: &mut App) -> Option<AppExit> {
+        for _ in 0..20 {
+            app.update();
+            if let Some(exit) = app.should_exit() {
+                return Some(exit);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn runs_piped_lines_and_exits_on_eof() {
+        let (mut app, sender, output) = app(true);
+        for line in ["sv_name", "", "sv_name \"server", "one\"", "bogus"] {
+            sender.send(line.to_string()).unwrap();
+        }
+        drop(sender);
+        assert_eq!(run(&mut app), Some(AppExit::Success));
+        assert_eq!(
+            output.text(),
+            "> sv_name\nsv_name = \"local\"\n  server name\n  default: \"local\"\n\
+             > sv_name \"server\n... one\"\nsv_name = \"server\\none\"\n\
+             > bogus\nUnknown command 'bogus'\n"
+        );
+    }
+
+    #[test]
+    fn keeps_running_without_exit_on_eof() {
+        let (mut app, sender, output) = app(false);
+        sender.send("sv_name".to_string()).unwrap();
+        drop(sender);
+        assert_eq!(run(&mut app), None);
+        assert!(output.text().starts_with("> sv_name\n"));
+    }
+
+    #[test]
+    fn unbalanced_quotes_in_a_variable_are_reported() {
+        let (mut app, sender, output) = app(true);
+        for line in ["let q '\"'", "sv_name $q", "let s $?"] {
+            sender.send(line.to_string()).unwrap();
+        }
+        drop(sender);
+        assert_eq!(run(&mut app), Some(AppExit::Success));
+        assert!(
+            output
+                .text()
+                .contains("> sv_name $q\nInvalid quoting\n> let s $?\ns = 2\n"),
+            "{}",
+            output.text()
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::prelude::*;
+use bevy::{
+    input::keyboard::{Key, KeyboardInput},
+    input_focus::InputFocus,
+};
+
+use super::systems::keyboard_input;
+
+/// Tracks whether a console is capturing keyboard input.
+///
+/// Gameplay systems can be gated on this with the [console_focused] and
+/// [console_not_focused] run conditions.
+#[derive(Resource, Debug, Default, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ConsoleInputCapture {
+    /// The console which currently has [InputFocus], if any.
+    pub focused: Option<Entity>,
+    /// When set, [ButtonInput<KeyCode>], [ButtonInput<Key>] and
+    /// [KeyboardInput] messages are cleared after the focused console has
+    /// processed them, so the rest of the app never sees them.
+    pub consume: bool,
+}
+impl ConsoleInputCapture {
+    pub fn is_captured(&self) -> bool {
+        self.focused.is_some()
+    }
+}
+
+/// Run condition which is true while a console has focus.
+pub fn console_focused(capture: Res<ConsoleInputCapture>) -> bool {
+    capture.is_captured()
+}
+
+/// Run condition which is true while no console has focus,
+/// e.g. `move_player.run_if(console_not_focused)`.
+pub fn console_not_focused(capture: Res<ConsoleInputCapture>) -> bool {
+    !capture.is_captured()
+}
+
+fn update_capture(
+    focus: Option<Res<InputFocus>>,
+    q_console: Query<(), With<Console>>,
+    mut capture: ResMut<ConsoleInputCapture>,
+) {
+    let focused = focus
+        .and_then(|focus| focus.get())
+        .filter(|entity| q_console.contains(*entity));
+    if capture.focused != focused {
+        capture.focused = focused;
+    }
+}
+
+/// Enables IME on every window while a console has focus, and restores each
+/// window's previous setting once it loses focus, so games can use IME too.
+fn update_ime(
+    capture: Res<ConsoleInputCapture>,
+    mut previous: Local<Option<Vec<(Entity, bool)>>>,
+    mut q_window: Query<(Entity, &mut Window)>,
+) {
+    match (capture.is_captured(), previous.take()) {
+        (true, None) => {
+            *previous = Some(
+                q_window
+                    .iter()
+                    .map(|(entity, window)| (entity, window.ime_enabled))
+                    .collect(),
+            );
+            for (_, mut window) in q_window.iter_mut() {
+                if !window.ime_enabled {
+                    window.ime_enabled = true;
+                }
+            }
+        }
+        (false, Some(windows)) => {
+            for (entity, enabled) in windows {
+                if let Ok((_, mut window)) = q_window.get_mut(entity) {
+                    window.ime_enabled = enabled;
+                }
+            }
+        }
+        (_, windows) => *previous = windows,
+    }
+}
+
+/// The console reads from its own copy of the keyboard state, so it is
+/// unaffected by this.
+fn consume_keyboard_input(
+    mut key_codes: ResMut<ButtonInput<KeyCode>>,
+    mut keys: ResMut<ButtonInput<Key>>,
+    mut messages: ResMut<Messages<KeyboardInput>>,
+) {
+    key_codes.reset_all();
+    keys.reset_all();
+    messages.clear();
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleInputCapture>();
+    app.add_systems(
+        PreUpdate,
+        (
+            update_capture.after(keyboard_input),
+            update_ime
+                .after(update_capture)
+                .run_if(resource_changed::<ConsoleInputCapture>),
+            consume_keyboard_input.after(update_capture).run_if(
+                resource_exists::<ButtonInput<KeyCode>>
+                    .and(console_focused)
+                    .and(|capture: Res<ConsoleInputCapture>| capture.consume),
+            ),
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConsolePlugin;
+
+    fn ime_enabled(app: &App, window: Entity) -> bool {
+        app.world().get::<Window>(window).unwrap().ime_enabled
+    }
+
+    #[test]
+    fn ime_is_restored_after_focus() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ConsolePlugin));
+        let console_id = app.world_mut().spawn(Console::default()).id();
+        let windows = [true, false].map(|ime_enabled| {
+            app.world_mut()
+                .spawn(Window {
+                    ime_enabled,
+                    ..default()
+                })
+                .id()
+        });
+        app.update();
+        assert!(ime_enabled(&app, windows[0]));
+        assert!(!ime_enabled(&app, windows[1]));
+
+        app.world_mut().resource_mut::<InputFocus>().set(console_id);
+        app.update();
+        assert!(ime_enabled(&app, windows[0]));
+        assert!(ime_enabled(&app, windows[1]));
+
+        // Toggling `consume` doesn't touch the windows.
+        app.world_mut()
+            .resource_mut::<ConsoleInputCapture>()
+            .consume = true;
+        app.update();
+        assert!(ime_enabled(&app, windows[1]));
+
+        app.world_mut().resource_mut::<InputFocus>().clear();
+        app.update();
+        assert!(ime_enabled(&app, windows[0]));
+        assert!(!ime_enabled(&app, windows[1]));
+    }
+}
@@ -5,6 +5,7 @@ use bevy::{
     input_focus::InputFocus,
     ui::ui_layout_system,
 };
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Component, Debug, Reflect, Clone)]
 #[component(immutable, on_insert=Self::on_insert)]
@@ -40,6 +41,9 @@ impl ConsoleUiSettings {
             )
         };
         world.commands().entity(ctx.entity).insert(bundle);
+        world.commands().queue(move |world: &mut World| {
+            ConsoleTextSpan::style(world, ctx.entity);
+        });
     }
     pub fn line_height(&self) -> f32 {
         match self.font.line_height {
@@ -47,6 +51,42 @@ impl ConsoleUiSettings {
             bevy::text::LineHeight::RelativeToFont(scale) => self.font.font_size * scale,
         }
     }
+    /// The number of characters which fit in `width` logical pixels, assuming
+    /// a monospace font such as the default Fira Mono with an advance of 0.6em.
+    pub fn visible_columns(&self, width: f32) -> usize {
+        (width / (self.font.font_size * 0.6)) as usize
+    }
+}
+
+/// Child spans of the console text which follow the input up to the cursor.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[require(TextSpan)]
+pub enum ConsoleTextSpan {
+    /// Uncommitted IME composition, highlighted with a background tint as
+    /// bevy's text has no underline.
+    Preedit,
+    /// Input after the cursor.
+    Tail,
+}
+impl ConsoleTextSpan {
+    fn style(world: &mut World, console_id: Entity) {
+        let Some(settings) = world.get::<ConsoleUiSettings>(console_id).cloned() else {
+            return;
+        };
+        let spans = world
+            .get::<Children>(console_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| Some((*child, *world.get::<ConsoleTextSpan>(*child)?)))
+            .collect::<Vec<_>>();
+        for (entity, span) in spans {
+            let mut entity = world.entity_mut(entity);
+            entity.insert((settings.font.clone(), TextColor(settings.font_color)));
+            if span == ConsoleTextSpan::Preedit {
+                entity.insert(TextBackgroundColor(settings.font_color.with_alpha(0.25)));
+            }
+        }
+    }
 }
 
 // TODO: Virtual scrolling requires custom scroll bar.
@@ -68,43 +108,70 @@ impl ConsoleBufferView {
         }
     }
     fn on_insert(mut world: DeferredWorld, ctx: HookContext) {
-        let text = {
+        let (text, preedit, tail) = {
             let view = world.get::<ConsoleBufferView>(ctx.entity).unwrap();
             let console = world.get::<Console>(ctx.entity).unwrap();
             view.text(console)
         };
-        world.commands().entity(ctx.entity).insert(text);
+        let spans = world
+            .get::<Children>(ctx.entity)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| Some((*child, *world.get::<ConsoleTextSpan>(*child)?)))
+            .collect::<Vec<_>>();
+        let mut commands = world.commands();
+        commands.entity(ctx.entity).insert(text);
+        for (entity, span) in spans {
+            let value = match span {
+                ConsoleTextSpan::Preedit => preedit.clone(),
+                ConsoleTextSpan::Tail => tail.clone(),
+            };
+            commands.entity(entity).insert(TextSpan(value));
+        }
     }
-    fn text(&self, console: &Console) -> impl Bundle {
+    /// Returns the text up to the cursor, the IME preedit, and the rest of the input.
+    fn text(&self, console: &Console) -> (Text, String, String) {
         let view = console
             .buffer
             .lines()
             .skip(self.start)
-            .take(self.range)
+            .take(self.rows(console))
             .collect::<Vec<&str>>()
             .join("\n");
-        Text(format!("{view}\n{}{}", console.prompt, console.input))
-    }
-    fn resize(
-        self,
-        container_height: f32,
-        line_height: f32,
-        lines: usize,
-        prompt_lines: usize,
-    ) -> Self {
+        let (head, tail) = console.input.split_at(console.cursor);
+        (
+            Text(format!(
+                "{}{view}\n{}",
+                console.pinned_text(),
+                console.format_input(head)
+            )),
+            console.preedit.clone(),
+            tail.replace('\n', &format!("\n{}", console.continuation_prompt)),
+        )
+    }
+    /// Number of buffer lines shown, which leaves room for pinned lines and
+    /// continuation lines of the input.
+    pub fn rows(&self, console: &Console) -> usize {
+        let extra_lines = console.input.matches('\n').count();
+        self.range
+            .saturating_sub(extra_lines + console.pinned_text().lines().count())
+    }
+    fn resize(self, container_height: f32, line_height: f32, prompt_lines: usize) -> Self {
         let range = ((container_height / line_height) as usize).saturating_sub(prompt_lines);
-        ConsoleBufferView {
-            start: lines.saturating_sub(range),
-            range,
-            ..self
-        }
+        ConsoleBufferView { range, ..self }
     }
     pub fn jump_to_bottom(self, console: &Console) -> Self {
         let count = console.buffer.lines().count();
         let prompt_size = console.prompt.lines().count();
-        let start = count.saturating_sub(self.range).saturating_add(prompt_size);
+        let start = count
+            .saturating_sub(self.rows(console))
+            .saturating_add(prompt_size);
         Self { start, ..self }
     }
+    /// Whether the newest line is shown.
+    pub fn is_at_bottom(&self, console: &Console) -> bool {
+        self.start >= self.jump_to_bottom(console).start
+    }
     pub(crate) fn on_resize(
         q: Query<
             (
@@ -119,19 +186,20 @@ impl ConsoleBufferView {
         mut commands: Commands,
     ) {
         for (entity, node, settings, console, view) in q {
-            let new_view = view.resize(
-                node.size().y,
-                settings.line_height(),
-                console.buffer.lines().count(),
-                console.prompt.lines().count(),
-            );
+            let new_view = view
+                .resize(
+                    node.size().y,
+                    settings.line_height(),
+                    console.prompt.lines().count(),
+                )
+                .jump_to_bottom(console);
             commands.entity(entity).insert(new_view);
         }
     }
 }
 
 #[derive(Component, Debug, Reflect, Clone)]
-#[require(Node, ConsoleUiSettings)]
+#[require(Node, ConsoleUiSettings, ConsoleSessionVars)]
 #[component(on_add=Self::on_add)]
 pub struct Console {
     /// raw output buffer
@@ -140,8 +208,15 @@ pub struct Console {
     pub(crate) buffer: String,
     pub(crate) input: String,
     pub prompt: String,
+    /// Shown in front of every input line after the first.
+    pub continuation_prompt: String,
     pub(crate) history: Vec<String>,
+    /// Byte offset into the input.
     pub(crate) cursor: usize,
+    /// Uncommitted IME composition, shown at the cursor.
+    pub(crate) preedit: String,
+    /// Lines shown above the scrollback by key, see [Console::pin].
+    pub(crate) pinned: Vec<(String, String)>,
 }
 impl Default for Console {
     fn default() -> Self {
@@ -149,8 +224,11 @@ impl Default for Console {
             buffer: Default::default(),
             input: Default::default(),
             prompt: "> ".into(),
+            continuation_prompt: "... ".into(),
             history: Default::default(),
             cursor: 0,
+            preedit: Default::default(),
+            pinned: Default::default(),
         }
     }
 }
@@ -158,6 +236,139 @@ impl Console {
     pub fn with_prompt(self, prompt: String) -> Self {
         Self { prompt, ..self }
     }
+    pub fn with_continuation_prompt(self, continuation_prompt: String) -> Self {
+        Self {
+            continuation_prompt,
+            ..self
+        }
+    }
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+    /// Replaces the input and moves the cursor to its end.
+    pub fn set_input(&mut self, input: String) {
+        self.cursor = input.len();
+        self.input = input;
+    }
+    /// Inserts text at the cursor.
+    pub fn insert(&mut self, text: &str) {
+        self.input.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+    /// Removes the grapheme cluster before the cursor.
+    pub fn delete_back(&mut self) {
+        if let Some(grapheme) = self.input[..self.cursor].graphemes(true).next_back() {
+            let start = self.cursor - grapheme.len();
+            self.input.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+    /// Removes everything between the cursor and the previous whitespace.
+    pub fn delete_word_back(&mut self) {
+        let start = self.input[..self.cursor]
+            .rfind(char::is_whitespace)
+            .unwrap_or_default();
+        self.input.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+    /// Clears the input, returning the submitted command line.
+    /// Backslash-newline pairs are removed, joining continued lines.
+    pub(crate) fn take_input(&mut self) -> String {
+        self.cursor = 0;
+        std::mem::take(&mut self.input).replace("\\\n", "")
+    }
+    /// True if submitting should insert a newline instead, i.e. if the input
+    /// has unbalanced quotes or ends with a backslash.
+    pub fn needs_continuation(&self) -> bool {
+        self.input.ends_with('\\') || shlex::split(&self.input).is_none()
+    }
+    /// Moves the cursor up (negative) or down (positive) by the given number
+    /// of input lines, keeping the column where possible.
+    /// Returns false if the cursor would leave the input.
+    pub fn move_cursor_lines(&mut self, delta: isize) -> bool {
+        let line_starts = std::iter::once(0)
+            .chain(self.input.match_indices('\n').map(|(i, _)| i + 1))
+            .collect::<Vec<_>>();
+        let line = line_starts
+            .iter()
+            .rposition(|start| *start <= self.cursor)
+            .unwrap_or_default();
+        let Some(target) = line
+            .checked_add_signed(delta)
+            .filter(|target| *target < line_starts.len())
+        else {
+            return false;
+        };
+        let column = self.input[line_starts[line]..self.cursor]
+            .graphemes(true)
+            .count();
+        let target_start = line_starts[target];
+        let target_line = self.input[target_start..]
+            .lines()
+            .next()
+            .unwrap_or_default();
+        self.cursor = target_start
+            + target_line
+                .grapheme_indices(true)
+                .nth(column)
+                .map(|(i, _)| i)
+                .unwrap_or(target_line.len());
+        true
+    }
+    /// Shows a line above the scrollback, replacing the line previously pinned
+    /// with the same key. The console is redrawn once its [ConsoleBufferView]
+    /// is reinserted.
+    pub fn pin(&mut self, key: impl Into<String>, line: impl Into<String>) {
+        let (key, line) = (key.into(), line.into());
+        match self.pinned.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, pinned)) => *pinned = line,
+            None => self.pinned.push((key, line)),
+        }
+    }
+    /// Removes a pinned line, returning false if there was none.
+    pub fn unpin(&mut self, key: &str) -> bool {
+        let len = self.pinned.len();
+        self.pinned.retain(|(k, _)| k != key);
+        self.pinned.len() != len
+    }
+    /// The pinned lines and their keys, in the order they were pinned.
+    pub fn pinned(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pinned
+            .iter()
+            .map(|(k, line)| (k.as_str(), line.as_str()))
+    }
+    /// The pinned lines followed by a rule, or nothing if there are none.
+    fn pinned_text(&self) -> String {
+        let Some(width) = self
+            .pinned
+            .iter()
+            .map(|(_, line)| line.chars().count())
+            .max()
+        else {
+            return String::new();
+        };
+        let mut text = self
+            .pinned
+            .iter()
+            .map(|(_, line)| format!("{line}\n"))
+            .collect::<String>();
+        text += &"─".repeat(width);
+        text.push('\n');
+        text
+    }
+    /// Number of lines taken up by the prompt and the current input.
+    pub fn prompt_lines(&self) -> usize {
+        self.prompt.lines().count() + self.input.matches('\n').count()
+    }
+    /// Formats an input string as it appears after the prompt,
+    /// with the continuation prompt in front of every following line.
+    pub fn format_input(&self, input: &str) -> String {
+        format!(
+            "{}{}",
+            self.prompt,
+            input.replace('\n', &format!("\n{}", self.continuation_prompt))
+        )
+    }
     pub(crate) fn on_add<'w>(mut world: DeferredWorld<'w>, ctx: HookContext) {
         let bundle = (
             Name::new("Console"),
@@ -171,6 +382,10 @@ impl Console {
             },
             ConsoleBufferView::new(ctx.entity),
         );
+        world.commands().spawn_batch(
+            [ConsoleTextSpan::Preedit, ConsoleTextSpan::Tail]
+                .map(|span| (span, ChildOf(ctx.entity))),
+        );
         world
             .commands()
             .entity(ctx.entity)
@@ -196,3 +411,80 @@ pub fn plugin(app: &mut App) {
         ConsoleBufferView::on_resize.after(ui_layout_system),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn console(input: &str) -> Console {
+        let mut console = Console::default();
+        console.set_input(input.to_string());
+        console
+    }
+
+    fn view_text(console: &Console, view: ConsoleBufferView) -> String {
+        view.text(console).0.0
+    }
+
+    #[test]
+    fn pinned_lines_keep_every_line_reachable() {
+        let mut console = console("");
+        console.buffer = (1..=10).map(|i| format!("\n{i}")).collect();
+        console.pin("fps", "fps: 60");
+        let view = ConsoleBufferView {
+            console_id: Entity::PLACEHOLDER,
+            start: 0,
+            range: 5,
+        };
+        assert_eq!(view.rows(&console), 3);
+        assert_eq!(view_text(&console, view), "fps: 60\n───────\n\n1\n2\n> ");
+        let bottom = view.jump_to_bottom(&console);
+        assert!(bottom.is_at_bottom(&console));
+        assert!(view_text(&console, bottom).ends_with("\n9\n10\n> "));
+    }
+
+    #[test]
+    fn continuation_lines_keep_the_newest_line_visible() {
+        let mut console = console("a\nb");
+        console.buffer = (1..=10).map(|i| format!("\n{i}")).collect();
+        let view = ConsoleBufferView {
+            console_id: Entity::PLACEHOLDER,
+            start: 0,
+            range: 5,
+        }
+        .jump_to_bottom(&console);
+        assert_eq!(view.rows(&console), 4);
+        assert!(view_text(&console, view).ends_with("\n10\n> a\n... b"));
+    }
+
+    #[test]
+    fn delete_back_removes_combining_marks() {
+        let mut console = console("cafe\u{301}");
+        console.delete_back();
+        assert_eq!(console.input(), "caf");
+        assert_eq!(console.cursor, 3);
+    }
+
+    #[test]
+    fn delete_back_removes_zwj_sequences() {
+        let family = "\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let mut console = console(&format!("hi {family}"));
+        console.delete_back();
+        assert_eq!(console.input(), "hi ");
+        console.set_input(format!("{family}{family}"));
+        console.delete_back();
+        assert_eq!(console.input(), family);
+    }
+
+    #[test]
+    fn delete_back_at_cursor() {
+        let mut console = console("ae\u{301}b");
+        console.cursor = "ae\u{301}".len();
+        console.delete_back();
+        assert_eq!(console.input(), "ab");
+        assert_eq!(console.cursor, 1);
+        console.cursor = 0;
+        console.delete_back();
+        assert_eq!(console.input(), "ab");
+    }
+}
@@ -2,17 +2,26 @@ use bevy::input_focus::InputFocus;
 
 use crate::prelude::*;
 
+mod capture;
 mod console;
 mod data;
 mod events;
+mod overlay;
 mod systems;
 
 pub mod prelude {
+    pub use super::capture::{ConsoleInputCapture, console_focused, console_not_focused};
     pub use super::console::*;
     pub use super::data::*;
+    pub use super::overlay::*;
 }
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins((events::plugin, systems::plugin, console::plugin));
+    app.add_plugins((
+        events::plugin,
+        systems::plugin,
+        console::plugin,
+        capture::plugin,
+    ));
     app.init_resource::<InputFocus>();
 }
@@ -0,0 +1,143 @@
+use crate::{ConsolePlugin, prelude::*};
+use bevy::{
+    input_focus::InputFocus,
+    math::curve::{Curve, EaseFunction},
+};
+
+use super::systems::{ConsoleKeyboard, keyboard_input, track_keyboard};
+
+/// Spawns a drop-down "Quake-style" console anchored to the top of the
+/// window.
+///
+/// The console slides in and out when [ConsoleOverlaySettings::toggle_key] is
+/// pressed. While it is open the console holds [InputFocus] and keyboard input
+/// is kept from reaching the rest of the app (see [ConsoleInputCapture::consume]).
+/// Adds [ConsolePlugin] if it has not been added already.
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleOverlayPlugin {
+    pub settings: ConsoleOverlaySettings,
+}
+impl Plugin for ConsoleOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<ConsolePlugin>() {
+            app.add_plugins(ConsolePlugin);
+        }
+        app.insert_resource(self.settings.clone());
+        app.world_mut()
+            .get_resource_or_init::<ConsoleInputCapture>()
+            .consume = true;
+        app.add_systems(Startup, spawn_overlay);
+        app.add_systems(
+            PreUpdate,
+            toggle_overlay.after(track_keyboard).before(keyboard_input),
+        );
+        app.add_systems(Update, animate_overlay);
+    }
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ConsoleOverlaySettings {
+    /// The physical key which opens and closes the overlay.
+    pub toggle_key: KeyCode,
+    /// Height of the overlay as a percentage of the viewport height.
+    pub height: f32,
+    /// Length of the slide animation in seconds.
+    pub slide_duration: f32,
+}
+impl Default for ConsoleOverlaySettings {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::Backquote,
+            height: 40.,
+            slide_duration: 0.15,
+        }
+    }
+}
+
+/// The root node of the drop-down console.
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct ConsoleOverlay {
+    pub console_id: Entity,
+    pub open: bool,
+    /// Animation progress, where 0 is fully closed and 1 is fully open.
+    pub progress: f32,
+    /// The entity which had focus before the overlay was opened.
+    pub previous_focus: Option<Entity>,
+}
+
+fn spawn_overlay(mut commands: Commands, settings: Res<ConsoleOverlaySettings>) {
+    let root = commands
+        .spawn((
+            Name::new("ConsoleOverlay"),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Vh(-settings.height),
+                left: Val::Px(0.),
+                width: Val::Vw(100.),
+                height: Val::Vh(settings.height),
+                ..Default::default()
+            },
+            GlobalZIndex(i32::MAX),
+            Visibility::Hidden,
+        ))
+        .id();
+    let console_id = commands.spawn((Console::default(), ChildOf(root))).id();
+    commands.entity(root).insert(ConsoleOverlay {
+        console_id,
+        open: false,
+        progress: 0.,
+        previous_focus: None,
+    });
+}
+
+fn toggle_overlay(
+    settings: Res<ConsoleOverlaySettings>,
+    mut keyboard: ResMut<ConsoleKeyboard>,
+    mut focus: ResMut<InputFocus>,
+    mut q_overlay: Query<&mut ConsoleOverlay>,
+) {
+    // Consume the key so that it doesn't get typed into the console.
+    if !keyboard.key_codes.clear_just_pressed(settings.toggle_key) {
+        return;
+    }
+    for mut overlay in q_overlay.iter_mut() {
+        overlay.open = !overlay.open;
+        if overlay.open {
+            overlay.previous_focus = focus.0;
+            focus.set(overlay.console_id);
+        } else if focus.0 == Some(overlay.console_id) {
+            focus.0 = overlay.previous_focus.take();
+        }
+    }
+}
+
+fn animate_overlay(
+    time: Res<Time<Real>>,
+    settings: Res<ConsoleOverlaySettings>,
+    mut q_overlay: Query<(&mut ConsoleOverlay, &mut Node, &mut Visibility)>,
+) {
+    for (mut overlay, mut node, mut visibility) in q_overlay.iter_mut() {
+        let target = if overlay.open { 1. } else { 0. };
+        if overlay.progress == target {
+            continue;
+        }
+        let step = if settings.slide_duration > 0. {
+            time.delta_secs() / settings.slide_duration
+        } else {
+            1.
+        };
+        overlay.progress = if overlay.open {
+            (overlay.progress + step).min(1.)
+        } else {
+            (overlay.progress - step).max(0.)
+        };
+        let eased = EaseFunction::CubicOut.sample_clamped(overlay.progress);
+        node.top = Val::Vh(-settings.height * (1. - eased));
+        *visibility = if overlay.progress > 0. {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
@@ -1,14 +1,58 @@
 use crate::prelude::*;
 use bevy::{
     input::{
-        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystems,
+        keyboard::{Key, KeyboardFocusLost, KeyboardInput},
         mouse::MouseScrollUnit,
     },
     input_focus::InputFocus,
     platform::collections::HashMap,
     ui::ui_layout_system,
+    window::Ime,
 };
 
+/// The keyboard as seen by the console.
+///
+/// This mirrors [ButtonInput<KeyCode>] and [ButtonInput<Key>] so that the
+/// global input resources can be consumed while a console is focused without
+/// the console itself losing track of held modifiers.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct ConsoleKeyboard {
+    pub key_codes: ButtonInput<KeyCode>,
+    pub keys: ButtonInput<Key>,
+    pub events: Vec<KeyboardInput>,
+}
+
+/// Mirrors [bevy::input::keyboard::keyboard_input_system].
+pub(crate) fn track_keyboard(
+    mut keyboard: ResMut<ConsoleKeyboard>,
+    mut input_events: MessageReader<KeyboardInput>,
+    mut focus_lost: MessageReader<KeyboardFocusLost>,
+) {
+    let keyboard = keyboard.as_mut();
+    keyboard.key_codes.clear();
+    keyboard.keys.clear();
+    keyboard.events.clear();
+    for event in input_events.read() {
+        match event.state {
+            ButtonState::Pressed => {
+                keyboard.key_codes.press(event.key_code);
+                keyboard.keys.press(event.logical_key.clone());
+            }
+            ButtonState::Released => {
+                keyboard.key_codes.release(event.key_code);
+                keyboard.keys.release(event.logical_key.clone());
+            }
+        }
+        keyboard.events.push(event.clone());
+    }
+    if !focus_lost.is_empty() {
+        keyboard.key_codes.release_all();
+        keyboard.keys.release_all();
+        focus_lost.clear();
+    }
+}
+
 /// submission event reader
 /// this must occur after [TextInputSystem](bevy_simple_text_input::TextInputSystem),
 /// but that's in the Update schedule so no need to worry
@@ -19,73 +63,39 @@ fn on_submit_msg(
 ) {
     for msg in reader.read() {
         if let Ok(mut console) = query.get_mut(msg.console_id) {
+            let input = console.take_input();
             commands.trigger(CallCommandEvent {
-                command_name: console.input.clone(),
+                command_name: input.clone(),
                 console_id: msg.console_id,
             });
-            let history_value = std::mem::take(&mut console.input);
-            console.history.push(history_value);
+            console.history.push(input);
         } else {
             error!("Could not submit from console with id {}", msg.console_id);
         }
     }
 }
 
-fn keyboard_input(
-    key_codes: Res<ButtonInput<KeyCode>>, // for modifiers
-    keys: Res<ButtonInput<Key>>,          // for modifiers
-    mut input_events: MessageReader<KeyboardInput>,
+pub(crate) fn keyboard_input(
+    keyboard: Res<ConsoleKeyboard>,
     actions: Res<ConsoleActionCache>,
     focus: Res<InputFocus>,
     mut q_console: Query<(&mut Console, &ConsoleBufferView)>,
     mut commands: Commands,
 ) {
-    if !input_events.is_empty()
+    let ConsoleKeyboard {
+        key_codes,
+        keys,
+        events,
+    } = keyboard.as_ref();
+    if !events.is_empty()
         && let Some(console_id) = focus.0
         && let Ok((mut console, buffer_view)) = q_console.get_mut(console_id)
     {
         let mut needs_refresh = false;
 
         let current_actions = actions.keys().filter_map(|action| {
-            if action
-                .bad_keys
-                .iter()
-                .any(|or_group| or_group.iter().any(|key| keys.just_pressed(key.clone())))
-            {
-                return None;
-            }
-            if action
-                .bad_mods
-                .iter()
-                .any(|or_group| or_group.iter().any(|key_code| key_codes.pressed(*key_code)))
-            {
-                return None;
-            }
-
-            let matched_keys = action.keys.iter().try_fold(vec![], |mut res, or_group| {
-                or_group
-                    .iter()
-                    .find(|key| keys.just_pressed((**key).clone()))
-                    .map(|found| {
-                        res.push(found.clone());
-                        res
-                    })
-            });
-
-            let matched_mods = action
-                .modifiers
-                .iter()
-                .try_fold(vec![], |mut res, or_group| {
-                    or_group
-                        .iter()
-                        .find(|key| key_codes.pressed(**key))
-                        .map(|found| {
-                            res.push(*found);
-                            res
-                        })
-                });
-            matched_keys
-                .zip(matched_mods)
+            action
+                .matches(keys, key_codes)
                 .map(|(keys, mods)| (action.clone(), keys, mods))
         });
         for (action, matched_keys, matched_mods) in current_actions {
@@ -106,22 +116,29 @@ fn keyboard_input(
 
         // assumes console buffer can be written to right now
         // this is always true in real terminals
-        for event in input_events.read() {
+        for event in events {
             if key_codes.just_pressed(event.key_code) {
                 match event.logical_key {
                     Key::Enter => {
-                        commands.write_message(ConsoleSubmitMsg { console_id });
+                        let shift =
+                            key_codes.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+                        if shift || console.needs_continuation() {
+                            console.insert("\n");
+                        } else {
+                            commands.write_message(ConsoleSubmitMsg { console_id });
+                        }
                         needs_refresh = true;
                     }
-                    Key::Character(ref c) => {
-                        console.input = format!("{}{}", console.input, c);
-                        needs_refresh = true;
+                    // use the composed text rather than the logical key so that
+                    // dead keys and non-latin layouts work
+                    _ => {
+                        if let Some(text) = event.text.as_ref()
+                            && !text.chars().any(char::is_control)
+                        {
+                            console.insert(text);
+                            needs_refresh = true;
+                        }
                     }
-                    Key::Space => {
-                        console.input.push(' ');
-                        needs_refresh = true;
-                    }
-                    _ => {}
                 }
             }
         }
@@ -134,6 +151,41 @@ fn keyboard_input(
     }
 }
 
+/// Handles IME composition, e.g. for CJK input.
+/// IME is enabled on the window while a console has focus.
+pub(crate) fn ime_input(
+    mut reader: MessageReader<Ime>,
+    focus: Res<InputFocus>,
+    mut q_console: Query<(&mut Console, &ConsoleBufferView)>,
+    mut commands: Commands,
+) {
+    let Some((console_id, (mut console, buffer_view))) = focus
+        .get()
+        .and_then(|id| q_console.get_mut(id).ok().map(|console| (id, console)))
+    else {
+        reader.clear();
+        return;
+    };
+    let mut needs_refresh = false;
+    for event in reader.read() {
+        match event {
+            Ime::Preedit { value, .. } => console.preedit = value.clone(),
+            Ime::Commit { value, .. } => {
+                console.preedit.clear();
+                console.insert(value);
+            }
+            Ime::Disabled { .. } => console.preedit.clear(),
+            Ime::Enabled { .. } => continue,
+        }
+        needs_refresh = true;
+    }
+    if needs_refresh {
+        commands
+            .entity(console_id)
+            .insert(buffer_view.jump_to_bottom(&console));
+    }
+}
+
 fn on_scroll(
     mut reader: MessageReader<ConsoleScrollMsg>,
     mut commands: Commands,
@@ -155,16 +207,13 @@ fn on_scroll(
     }
     for (console_id, delta) in map.into_iter() {
         let (console, view) = console_q.get(console_id).unwrap();
-        let range = view.range;
-        let buffer_size = console.buffer.lines().count();
-        let prompt_size = console.prompt.lines().count();
-        if buffer_size <= range {
+        if console.buffer.lines().count() <= view.rows(console) {
             continue; // disable scroll. this should be handled elsewhere.
         }
         let start = view
             .start
             .saturating_add_signed(delta.y as isize)
-            .min(buffer_size - range + prompt_size);
+            .min(view.jump_to_bottom(console).start);
         let new_view = ConsoleBufferView { start, ..*view };
         commands.entity(console_id).insert(new_view);
     }
@@ -174,14 +223,131 @@ pub fn plugin(app: &mut App) {
     app.add_message::<ConsolePrintln>();
     app.add_message::<ConsoleScrollMsg>();
     app.add_message::<ConsoleSubmitMsg>();
+    app.init_resource::<ConsoleKeyboard>();
     app.add_systems(
         PostUpdate,
         (
-            keyboard_input.run_if(
-                resource_changed::<ButtonInput<KeyCode>>.and(resource_exists::<InputFocus>),
-            ),
             on_submit_msg.before(ui_layout_system),
             on_scroll.before(ui_layout_system),
         ),
     );
+    app.add_systems(
+        PreUpdate,
+        (
+            track_keyboard.after(InputSystems),
+            keyboard_input
+                .after(track_keyboard)
+                .run_if(resource_exists::<InputFocus>),
+            // `Ime` is only added by the `WindowPlugin`.
+            ime_input
+                .after(keyboard_input)
+                .run_if(resource_exists::<InputFocus>.and(resource_exists::<Messages<Ime>>)),
+        )
+            // Headless apps, e.g. with the StdinConsolePlugin, have no keyboard.
+            .run_if(resource_exists::<ButtonInput<KeyCode>>),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::InputPlugin;
+
+    use super::*;
+    use crate::ConsolePlugin;
+
+    fn app() -> (App, Entity) {
+        let mut app = App::new();
+        // The console works when it is added before the plugins it reads from.
+        app.add_plugins((ConsolePlugin, MinimalPlugins, InputPlugin));
+        app.add_message::<Ime>();
+        let console_id = app.world_mut().spawn(Console::default()).id();
+        app.world_mut().resource_mut::<InputFocus>().set(console_id);
+        app.update();
+        (app, console_id)
+    }
+
+    /// Presses and releases a key over two frames.
+    fn type_key(app: &mut App, key_code: KeyCode, logical_key: Key, text: Option<&str>) {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            app.world_mut().write_message(KeyboardInput {
+                key_code,
+                logical_key: logical_key.clone(),
+                state,
+                text: text
+                    .filter(|_| state == ButtonState::Pressed)
+                    .map(Into::into),
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+            });
+            app.update();
+        }
+    }
+
+    fn console(app: &App, console_id: Entity) -> &Console {
+        app.world().get::<Console>(console_id).unwrap()
+    }
+
+    #[test]
+    fn ime_preedit_and_commit() {
+        let (mut app, console_id) = app();
+        app.world_mut().write_message(Ime::Preedit {
+            window: Entity::PLACEHOLDER,
+            value: "にほ".to_string(),
+            cursor: Some((6, 6)),
+        });
+        app.update();
+        assert_eq!(console(&app, console_id).preedit, "にほ");
+        assert_eq!(console(&app, console_id).input(), "");
+
+        app.world_mut().write_message(Ime::Commit {
+            window: Entity::PLACEHOLDER,
+            value: "日本".to_string(),
+        });
+        app.update();
+        assert_eq!(console(&app, console_id).preedit, "");
+        assert_eq!(console(&app, console_id).input(), "日本");
+    }
+
+    #[test]
+    fn ime_disabled_clears_preedit() {
+        let (mut app, console_id) = app();
+        app.world_mut().write_message(Ime::Preedit {
+            window: Entity::PLACEHOLDER,
+            value: "に".to_string(),
+            cursor: None,
+        });
+        app.world_mut().write_message(Ime::Disabled {
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        assert_eq!(console(&app, console_id).preedit, "");
+        assert_eq!(console(&app, console_id).input(), "");
+    }
+
+    #[test]
+    fn dead_keys_insert_composed_text() {
+        let (mut app, console_id) = app();
+        type_key(&mut app, KeyCode::Quote, Key::Dead(Some('´')), None);
+        assert_eq!(console(&app, console_id).input(), "");
+        type_key(
+            &mut app,
+            KeyCode::KeyE,
+            Key::Character("é".into()),
+            Some("é"),
+        );
+        assert_eq!(console(&app, console_id).input(), "é");
+    }
+
+    #[test]
+    fn control_text_is_not_inserted() {
+        let (mut app, console_id) = app();
+        type_key(&mut app, KeyCode::Tab, Key::Tab, Some("\t"));
+        type_key(
+            &mut app,
+            KeyCode::KeyA,
+            Key::Character("a".into()),
+            Some("a"),
+        );
+        assert_eq!(console(&app, console_id).input(), "a");
+    }
 }